@@ -19,13 +19,21 @@ use ink_lang as ink;
 pub use self::erc20::Erc20;
 pub use self::erc20::Error;
 pub use self::erc20::Result;
+pub use self::erc20::ON_COMPANION_TRANSFER_SELECTOR;
+pub use self::erc20::ON_TOKENS_RECEIVED_SELECTOR;
 
 #[ink::contract]
 mod erc20 {
+    #[cfg(not(any(test, feature = "ink-as-dependency")))]
+    use ink_env::call::{build_call, utils::ReturnType, ExecutionInput, Selector};
+    use ink_prelude::{string::String, vec::Vec};
     #[cfg(not(feature = "ink-as-dependency"))]
     use ink_storage::{collections::HashMap as StorageHashMap, lazy::Lazy};
 
-    /// A simple ERC-20 contract.
+    /// A simple ERC-20 contract exposing the PSP22 interface.
+    ///
+    /// The messages of the PSP22 standard use the selectors of the standard, so
+    /// that contracts built against this crate can call any PSP22 token.
     #[ink(storage)]
     pub struct Erc20 {
         /// Total token supply.
@@ -35,6 +43,36 @@ mod erc20 {
         /// Mapping of the token amount which an account is allowed to withdraw
         /// from another account.
         allowances: StorageHashMap<(AccountId, AccountId), Balance>,
+        /// Name of the token, if any.
+        name: Lazy<Option<String>>,
+        /// Symbol of the token, if any.
+        symbol: Lazy<Option<String>>,
+        /// Number of decimals used for display purposes.
+        decimals: Lazy<u8>,
+        /// The account allowed to mint and burn tokens, if any.
+        minter: Lazy<Option<AccountId>>,
+        /// Maximum total supply of the token, if any.
+        cap: Lazy<Option<Balance>>,
+        /// The account allowed to pause and unpause the token, if any.
+        pauser: Lazy<Option<AccountId>>,
+        /// Whether transfers and approvals are currently stopped.
+        paused: Lazy<bool>,
+        /// The id of the most recent snapshot, `0` if none was taken yet.
+        current_snapshot_id: Lazy<SnapshotId>,
+        /// Mapping from owner and index to the checkpoints of its balance,
+        /// sorted by snapshot id.
+        account_checkpoints: StorageHashMap<(AccountId, u32), (SnapshotId, Balance)>,
+        /// Mapping from owner to the number of checkpoints of its balance.
+        account_checkpoint_counts: StorageHashMap<AccountId, u32>,
+        /// Mapping from index to the checkpoints of the total supply, sorted by
+        /// snapshot id.
+        total_supply_checkpoints: StorageHashMap<u32, (SnapshotId, Balance)>,
+        /// The number of checkpoints of the total supply.
+        total_supply_checkpoint_count: Lazy<u32>,
+        /// Whether holders can transfer their tokens.
+        transferable: Lazy<bool>,
+        /// The contract notified of and able to reject every transfer, if any.
+        controller: Lazy<Option<AccountId>>,
     }
 
     /// Event emitted when a token transfer occurs.
@@ -60,23 +98,166 @@ mod erc20 {
         value: Balance,
     }
 
+    /// Event emitted when the minter role is transferred or renounced.
+    #[ink(event)]
+    pub struct MinterTransferred {
+        #[ink(topic)]
+        previous_minter: Option<AccountId>,
+        #[ink(topic)]
+        new_minter: Option<AccountId>,
+    }
+
+    /// Event emitted when the token is paused by `account`.
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Event emitted when the token is unpaused by `account`.
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Event emitted when a snapshot with the given `id` is taken.
+    #[ink(event)]
+    pub struct Snapshot {
+        #[ink(topic)]
+        id: SnapshotId,
+    }
+
     /// The ERC-20 error types.
+    ///
+    /// The first variants follow the layout of the PSP22 error type so that the
+    /// errors of any PSP22 token decode into this type.
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
+        /// Custom error type for cases not covered by the other variants.
+        Custom(String),
         /// Returned if not enough balance to fulfill a request is available.
         InsufficientBalance,
         /// Returned if not enough allowance to fulfill a request is available.
         InsufficientAllowance,
+        /// Returned if the recipient's address is zero.
+        ZeroRecipientAddress,
+        /// Returned if the sender's address is zero.
+        ZeroSenderAddress,
+        /// Returned if the safe transfer check fails.
+        SafeTransferCheckFailed(String),
+        /// Returned if the caller does not hold the minter role.
+        NotMinter,
+        /// Returned if minting would exceed the cap of the total supply.
+        CapExceeded,
+        /// Returned if the caller does not hold the pauser role.
+        NotPauser,
+        /// Returned if the token is paused.
+        Paused,
+        /// Returned if increasing an allowance would overflow.
+        AllowanceOverflow,
+        /// Returned if an allowance would be decreased below zero.
+        AllowanceUnderflow,
+        /// Returned if a batch holds more than `MAX_BATCH_SIZE` transfers.
+        BatchTooLarge,
+        /// Returned if the token cannot be transferred by its holders.
+        NonTransferable,
     }
 
     /// The ERC-20 result type.
     pub type Result<T> = core::result::Result<T, self::Error>;
 
+    /// The identifier of a balance snapshot.
+    pub type SnapshotId = u32;
+
+    /// The maximum number of transfers in a single batch.
+    pub const MAX_BATCH_SIZE: usize = 100;
+
+    /// The selector of the `on_tokens_received` message a contract implements to
+    /// be notified of the tokens it receives.
+    ///
+    /// The message takes the `operator`, `from`, `value` and `data` of the transfer
+    /// and returns a `Result<(), String>`. Returning an error rejects the transfer.
+    pub const ON_TOKENS_RECEIVED_SELECTOR: [u8; 4] = [0xa4, 0x97, 0x13, 0x9d];
+
+    /// The selector of the `on_companion_transfer` message of the controller of
+    /// a companion token.
+    ///
+    /// The message takes the `from`, `to` and `value` of the transfer and returns
+    /// a `Result<(), String>`. Returning an error rejects the transfer.
+    pub const ON_COMPANION_TRANSFER_SELECTOR: [u8; 4] = [0x5b, 0xf6, 0x8d, 0x11];
+
+    #[cfg(test)]
+    thread_local! {
+        /// What the receiver of the next notified transfer does in unit tests.
+        static RECEIVER_HOOK: core::cell::RefCell<Option<fn(&mut Erc20)>> =
+            core::cell::RefCell::new(None);
+    }
+
     impl Erc20 {
         /// Creates a new ERC-20 contract with the specified initial supply.
         #[ink(constructor)]
         pub fn new(initial_supply: Balance) -> Self {
+            Self::new_with_metadata(initial_supply, None, None, 0)
+        }
+
+        /// Creates a new ERC-20 contract with the specified initial supply and
+        /// the optional `name`, `symbol` and `decimals` of the token.
+        #[ink(constructor)]
+        pub fn new_with_metadata(
+            initial_supply: Balance,
+            name: Option<String>,
+            symbol: Option<String>,
+            decimals: u8,
+        ) -> Self {
+            Self::init(initial_supply, None, name, symbol, decimals)
+        }
+
+        /// Creates a new ERC-20 contract whose total supply can never exceed `cap`.
+        ///
+        /// # Panics
+        ///
+        /// If `initial_supply` exceeds `cap`.
+        #[ink(constructor)]
+        pub fn new_capped(
+            initial_supply: Balance,
+            cap: Balance,
+            name: Option<String>,
+            symbol: Option<String>,
+            decimals: u8,
+        ) -> Self {
+            assert!(initial_supply <= cap, "initial supply exceeds cap");
+            Self::init(initial_supply, Some(cap), name, symbol, decimals)
+        }
+
+        /// Creates a companion token without supply, which mirrors balances kept
+        /// by the calling contract.
+        ///
+        /// The caller becomes the minter. If the token is `transferable` the caller
+        /// becomes its controller and is notified of every transfer.
+        #[ink(constructor)]
+        pub fn new_companion(
+            name: Option<String>,
+            symbol: Option<String>,
+            transferable: bool,
+        ) -> Self {
+            let mut instance = Self::init(0, None, name, symbol, 0);
+            *instance.transferable = transferable;
+            if transferable {
+                *instance.controller = Some(Self::env().caller());
+            }
+            instance
+        }
+
+        /// Mints `initial_supply` to the caller, who becomes the minter.
+        fn init(
+            initial_supply: Balance,
+            cap: Option<Balance>,
+            name: Option<String>,
+            symbol: Option<String>,
+            decimals: u8,
+        ) -> Self {
             let caller = Self::env().caller();
             let mut balances = StorageHashMap::new();
             balances.insert(caller, initial_supply);
@@ -84,6 +265,20 @@ mod erc20 {
                 total_supply: Lazy::new(initial_supply),
                 balances,
                 allowances: StorageHashMap::new(),
+                name: Lazy::new(name),
+                symbol: Lazy::new(symbol),
+                decimals: Lazy::new(decimals),
+                minter: Lazy::new(Some(caller)),
+                cap: Lazy::new(cap),
+                pauser: Lazy::new(Some(caller)),
+                paused: Lazy::new(false),
+                current_snapshot_id: Lazy::new(0),
+                account_checkpoints: StorageHashMap::new(),
+                account_checkpoint_counts: StorageHashMap::new(),
+                total_supply_checkpoints: StorageHashMap::new(),
+                total_supply_checkpoint_count: Lazy::new(0),
+                transferable: Lazy::new(true),
+                controller: Lazy::new(None),
             };
             Self::env().emit_event(Transfer {
                 from: None,
@@ -93,8 +288,68 @@ mod erc20 {
             instance
         }
 
+        /// Returns the name of the token, if any.
+        #[ink(message)]
+        #[ink(selector = "0x3d261bd4")]
+        pub fn token_name(&self) -> Option<String> {
+            (*self.name).clone()
+        }
+
+        /// Returns the symbol of the token, if any.
+        #[ink(message)]
+        #[ink(selector = "0x34205be5")]
+        pub fn token_symbol(&self) -> Option<String> {
+            (*self.symbol).clone()
+        }
+
+        /// Returns the number of decimals of the token.
+        ///
+        /// Returns `0` if no decimals were set on construction.
+        #[ink(message)]
+        #[ink(selector = "0x7271b782")]
+        pub fn token_decimals(&self) -> u8 {
+            *self.decimals
+        }
+
+        /// Returns the account allowed to mint and burn tokens, if any.
+        #[ink(message)]
+        pub fn minter(&self) -> Option<AccountId> {
+            *self.minter
+        }
+
+        /// Returns the maximum total supply of the token, if any.
+        #[ink(message)]
+        pub fn cap(&self) -> Option<Balance> {
+            *self.cap
+        }
+
+        /// Returns the account allowed to pause and unpause the token, if any.
+        #[ink(message)]
+        pub fn pauser(&self) -> Option<AccountId> {
+            *self.pauser
+        }
+
+        /// Returns `true` if transfers and approvals are currently stopped.
+        #[ink(message)]
+        pub fn paused(&self) -> bool {
+            *self.paused
+        }
+
+        /// Returns `true` if holders can transfer their tokens.
+        #[ink(message)]
+        pub fn transferable(&self) -> bool {
+            *self.transferable
+        }
+
+        /// Returns the contract notified of and able to reject every transfer, if any.
+        #[ink(message)]
+        pub fn controller(&self) -> Option<AccountId> {
+            *self.controller
+        }
+
         /// Returns the total token supply.
         #[ink(message)]
+        #[ink(selector = "0x162df8c2")]
         pub fn total_supply(&self) -> Balance {
             *self.total_supply
         }
@@ -103,6 +358,7 @@ mod erc20 {
         ///
         /// Returns `0` if the account is non-existent.
         #[ink(message)]
+        #[ink(selector = "0x6568382f")]
         pub fn balance_of(&self, owner: AccountId) -> Balance {
             self.balances.get(&owner).copied().unwrap_or(0)
         }
@@ -111,22 +367,41 @@ mod erc20 {
         ///
         /// Returns `0` if no allowance has been set `0`.
         #[ink(message)]
+        #[ink(selector = "0x4d47d921")]
         pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
             self.allowances.get(&(owner, spender)).copied().unwrap_or(0)
         }
 
         /// Transfers `value` amount of tokens from the caller's account to account `to`.
         ///
+        /// If `data` is not empty, `to` is notified of the transfer through its
+        /// `on_tokens_received` message along with `data`.
+        ///
         /// On success a `Transfer` event is emitted.
         ///
         /// # Errors
         ///
         /// Returns `InsufficientBalance` error if there are not enough tokens on
         /// the caller's account balance.
+        ///
+        /// Returns `ZeroRecipientAddress` error if `to` is the zero address.
+        ///
+        /// Returns `Custom` error if the controller rejects the transfer.
+        ///
+        /// Returns `NonTransferable` error if the token is not transferable.
+        ///
+        /// Returns `Paused` error if the token is paused.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `data` is not empty and `to` rejects the transfer.
         #[ink(message)]
-        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+        #[ink(selector = "0xdb20f9f5")]
+        pub fn transfer(&mut self, to: AccountId, value: Balance, data: Vec<u8>) -> Result<()> {
+            self.ensure_not_paused()?;
+            self.ensure_transferable()?;
             let from = self.env().caller();
-            self.transfer_from_to(from, to, value)
+            self.transfer_from_to(from, to, value, data)
         }
 
         /// Allows `spender` to withdraw from the caller's account multiple times, up to
@@ -135,9 +410,14 @@ mod erc20 {
         /// If this function is called again it overwrites the current allowance with `value`.
         ///
         /// An `Approval` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Paused` error if the token is paused.
         #[ink(message)]
-        #[ink(selector = "0xDEADBEEE")]
+        #[ink(selector = "0xb20f1bbd")]
         pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            self.ensure_not_paused()?;
             let owner = self.env().caller();
             self.allowances.insert((owner, spender), value);
             self.env().emit_event(Approval {
@@ -153,7 +433,12 @@ mod erc20 {
         /// This can be used to allow a contract to transfer tokens on ones behalf and/or
         /// to charge fees in sub-currencies, for example.
         ///
-        /// On success a `Transfer` event is emitted.
+        /// If `data` is not empty, `to` is notified of the transfer through its
+        /// `on_tokens_received` message along with `data`.
+        ///
+        /// On success an `Approval` event with the remaining allowance of the caller
+        /// is emitted, followed by a `Transfer` event. The allowance is spent before
+        /// the controller or `to` are called, so that they cannot spend it again.
         ///
         /// # Errors
         ///
@@ -162,42 +447,213 @@ mod erc20 {
         ///
         /// Returns `InsufficientBalance` error if there are not enough tokens on
         /// the the account balance of `from`.
+        ///
+        /// Returns `ZeroSenderAddress` or `ZeroRecipientAddress` error if `from`
+        /// or `to` is the zero address.
+        ///
+        /// Returns `Custom` error if the controller rejects the transfer.
+        ///
+        /// Returns `NonTransferable` error if the token is not transferable.
+        ///
+        /// Returns `Paused` error if the token is paused.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `data` is not empty and `to` rejects the transfer.
         #[ink(message)]
-        #[ink(selector = "0xDEADBEEF")]
+        #[ink(selector = "0x54b3c76e")]
         pub fn transfer_from(
             &mut self,
             from: AccountId,
             to: AccountId,
             value: Balance,
+            data: Vec<u8>,
         ) -> Result<()> {
+            self.ensure_not_paused()?;
+            self.ensure_transferable()?;
             let caller = self.env().caller();
             let allowance = self.allowance(from, caller);
             if allowance < value {
                 return Err(Error::InsufficientAllowance);
             }
-            self.transfer_from_to(from, to, value)?;
+            self.ensure_can_transfer(from, to, value)?;
+            // spend the allowance before the controller and the receiver are called,
+            // so that they cannot re-enter with the allowance still available
             self.allowances.insert((from, caller), allowance - value);
+            self.env().emit_event(Approval {
+                owner: from,
+                spender: caller,
+                value: allowance - value,
+            });
+            if let Err(error) = self.transfer_from_to(from, to, value, data) {
+                // the controller rejected the transfer before any tokens moved
+                self.allowances.insert((from, caller), allowance);
+                self.env().emit_event(Approval {
+                    owner: from,
+                    spender: caller,
+                    value: allowance,
+                });
+                return Err(error);
+            }
+            Ok(())
+        }
+
+        /// Transfers tokens from the caller's account to each `(to, value)` of
+        /// `transfers`.
+        ///
+        /// The batch is atomic, either all transfers succeed or none is made. The
+        /// recipients are not notified through `on_tokens_received`.
+        ///
+        /// On success a `Transfer` event is emitted per transfer.
+        ///
+        /// # Errors
+        ///
+        /// Returns `BatchTooLarge` error if there are more than `MAX_BATCH_SIZE`
+        /// transfers.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance for all transfers.
+        ///
+        /// Returns `ZeroRecipientAddress` error if any recipient is the zero address.
+        ///
+        /// Returns `NonTransferable` error if the token is not transferable or has
+        /// a controller.
+        ///
+        /// Returns `Paused` error if the token is paused.
+        #[ink(message)]
+        pub fn batch_transfer(&mut self, transfers: Vec<(AccountId, Balance)>) -> Result<()> {
+            self.ensure_not_paused()?;
+            let from = self.env().caller();
+            self.batch_total(from, &transfers)?;
+            self.batch_transfer_from_to(from, transfers)
+        }
+
+        /// Transfers tokens on the behalf of `from` to each `(to, value)` of
+        /// `transfers`.
+        ///
+        /// The batch is atomic, either all transfers succeed or none is made. The
+        /// recipients are not notified through `on_tokens_received`.
+        ///
+        /// On success a `Transfer` event is emitted per transfer, followed by an
+        /// `Approval` event with the remaining allowance of the caller.
+        ///
+        /// # Errors
+        ///
+        /// Returns `BatchTooLarge` error if there are more than `MAX_BATCH_SIZE`
+        /// transfers.
+        ///
+        /// Returns `InsufficientAllowance` error if there are not enough tokens allowed
+        /// for the caller to withdraw from `from` for all transfers.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the account balance of `from` for all transfers.
+        ///
+        /// Returns `ZeroSenderAddress` or `ZeroRecipientAddress` error if `from`
+        /// or any recipient is the zero address.
+        ///
+        /// Returns `NonTransferable` error if the token is not transferable or has
+        /// a controller.
+        ///
+        /// Returns `Paused` error if the token is paused.
+        #[ink(message)]
+        pub fn batch_transfer_from(
+            &mut self,
+            from: AccountId,
+            transfers: Vec<(AccountId, Balance)>,
+        ) -> Result<()> {
+            self.ensure_not_paused()?;
+            let caller = self.env().caller();
+            let allowance = self.allowance(from, caller);
+            let total = self.batch_total(from, &transfers)?;
+            if allowance < total {
+                return Err(Error::InsufficientAllowance);
+            }
+            self.batch_transfer_from_to(from, transfers)?;
+            self.allowances.insert((from, caller), allowance - total);
+            self.env().emit_event(Approval {
+                owner: from,
+                spender: caller,
+                value: allowance - total,
+            });
+            Ok(())
+        }
+
+        /// Checks that all `transfers` from `from` can be made and returns the
+        /// total value transferred.
+        fn batch_total(
+            &self,
+            from: AccountId,
+            transfers: &[(AccountId, Balance)],
+        ) -> Result<Balance> {
+            // a controller could reject any transfer after the first ones were made
+            if !self.transferable() || self.controller().is_some() {
+                return Err(Error::NonTransferable);
+            }
+            if transfers.len() > MAX_BATCH_SIZE {
+                return Err(Error::BatchTooLarge);
+            }
+            if from == AccountId::from([0x0; 32]) {
+                return Err(Error::ZeroSenderAddress);
+            }
+            let mut total: Balance = 0;
+            for &(to, value) in transfers {
+                if to == AccountId::from([0x0; 32]) {
+                    return Err(Error::ZeroRecipientAddress);
+                }
+                total = total.checked_add(value).ok_or(Error::InsufficientBalance)?;
+            }
+            if self.balance_of(from) < total {
+                return Err(Error::InsufficientBalance);
+            }
+            Ok(total)
+        }
+
+        fn batch_transfer_from_to(
+            &mut self,
+            from: AccountId,
+            transfers: Vec<(AccountId, Balance)>,
+        ) -> Result<()> {
+            for (to, value) in transfers {
+                self.transfer_from_to(from, to, value, Vec::new())?;
+            }
             Ok(())
         }
 
         /// Transfers `value` amount of tokens from the caller's account to account `to`.
         ///
+        /// The controller, if any, is notified of the transfer through
+        /// `on_companion_transfer` before any balance changes. If `data` is not
+        /// empty, `to` is notified through `on_tokens_received` once the tokens are
+        /// credited.
+        ///
         /// On success a `Transfer` event is emitted.
         ///
         /// # Errors
         ///
         /// Returns `InsufficientBalance` error if there are not enough tokens on
         /// the caller's account balance.
+        ///
+        /// Returns `ZeroSenderAddress` or `ZeroRecipientAddress` error if `from`
+        /// or `to` is the zero address.
+        ///
+        /// Returns `Custom` error if the controller rejects the transfer.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `to` rejects the transfer or cannot be notified, which
+        /// reverts the whole call including whatever `to` did in the meantime.
         fn transfer_from_to(
             &mut self,
             from: AccountId,
             to: AccountId,
             value: Balance,
+            data: Vec<u8>,
         ) -> Result<()> {
+            self.ensure_can_transfer(from, to, value)?;
+            self.notify_controller(from, to, value)?;
             let from_balance = self.balance_of(from);
-            if from_balance < value {
-                return Err(Error::InsufficientBalance);
-            }
+            self.update_account_snapshot(from);
+            self.update_account_snapshot(to);
             self.balances.insert(from, from_balance - value);
             let to_balance = self.balance_of(to);
             self.balances.insert(to, to_balance + value);
@@ -206,76 +662,510 @@ mod erc20 {
                 to: Some(to),
                 value,
             });
+            if !data.is_empty() {
+                if let Err(error) = self.notify_receiver(from, to, value, data) {
+                    panic!("transfer rejected by the receiver: {:?}", error);
+                }
+            }
             Ok(())
         }
 
-        /**
-         * @dev Increase the amount of tokens that an owner allowed to a spender.
-         * approve should be called when allowed_[_spender] == 0. To increment
-         * allowed value is better to use this function to avoid 2 calls (and wait until
-         * the first transaction is mined)
-         * From MonolithDAO Token.sol
-         * Emits an Approval event.
-         * @param spender The address which will spend the funds.
-         * @param addedValue The amount of tokens to increase the allowance by.
-         */
+        /// Creates `value` new tokens on the account `to`.
+        ///
+        /// On success a `Transfer` event from `None` is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotMinter` error if the caller is not the minter.
+        ///
+        /// Returns `CapExceeded` error if the total supply would exceed the cap.
         #[ink(message)]
-        pub fn increase_allowance(&mut self, spender: AccountId, added_value: u128) -> Result<()> {
-            let owner = self.env().caller();
-            let old_value = self
-                .allowances
-                .get(&(owner, spender))
-                .unwrap_or(&0);
-            self.approve(spender, old_value + added_value)
+        pub fn mint(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            self.ensure_minter()?;
+            let total_supply = self
+                .total_supply()
+                .checked_add(value)
+                .ok_or(Error::CapExceeded)?;
+            if self.cap().map_or(false, |cap| total_supply > cap) {
+                return Err(Error::CapExceeded);
+            }
+            self.update_account_snapshot(to);
+            self.update_total_supply_snapshot();
+            *self.total_supply = total_supply;
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, to_balance + value);
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(to),
+                value,
+            });
+            Ok(())
         }
 
-        /**
-         * @dev Decrease the amount of tokens that an owner allowed to a spender.
-         * approve should be called when allowed_[_spender] == 0. To decrement
-         * allowed value is better to use this function to avoid 2 calls (and wait until
-         * the first transaction is mined)
-         * From MonolithDAO Token.sol
-         * Emits an Approval event.
-         * @param spender The address which will spend the funds.
-         * @param subtractedValue The amount of tokens to decrease the allowance by.
-         */
+        /// Destroys `value` tokens from the caller's account.
+        ///
+        /// On success a `Transfer` event to `None` is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotMinter` error if the caller is not the minter.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance.
         #[ink(message)]
-        pub fn decrease_allowance(
-            &mut self,
-            spender: AccountId,
-            subtracted_value: u128,
-        ) -> Result<()> {
-            // _allowed[msg.sender][spender].sub(subtracted_value),
-            let owner = self.env().caller();
-            let old_value = self
-                .allowances
-                .get(&(owner, spender))
-                .unwrap_or(&0);
-            self.approve(spender, old_value - subtracted_value)
+        pub fn burn(&mut self, value: Balance) -> Result<()> {
+            let caller = self.env().caller();
+            self.burn_from(caller, value)
         }
-    }
 
-    /// Unit tests.
-    #[cfg(test)]
-    mod tests {
-        /// Imports all the definitions from the outer scope so we can use them here.
-        use super::*;
-        use ink_env::{
-            hash::{Blake2x256, CryptoHash, HashOutput},
-            Clear,
-        };
+        /// Destroys `value` tokens from the account `from`.
+        ///
+        /// The minter does not need an allowance to burn tokens.
+        ///
+        /// On success a `Transfer` event to `None` is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotMinter` error if the caller is not the minter.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the account balance of `from`.
+        #[ink(message)]
+        pub fn burn_from(&mut self, from: AccountId, value: Balance) -> Result<()> {
+            self.ensure_minter()?;
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance);
+            }
+            self.update_account_snapshot(from);
+            self.update_total_supply_snapshot();
+            self.balances.insert(from, from_balance - value);
+            let total_supply = self.total_supply() - value;
+            *self.total_supply = total_supply;
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: None,
+                value,
+            });
+            Ok(())
+        }
 
-        type Event = <Erc20 as ::ink_lang::BaseEvent>::Type;
+        /// Transfers the minter role from the caller to `new_minter`.
+        ///
+        /// On success a `MinterTransferred` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotMinter` error if the caller is not the minter.
+        #[ink(message)]
+        pub fn transfer_minter_role(&mut self, new_minter: AccountId) -> Result<()> {
+            self.ensure_minter()?;
+            self.set_minter(Some(new_minter));
+            Ok(())
+        }
 
-        use ink_lang as ink;
+        /// Gives up the minter role, no tokens can be minted or burned afterwards.
+        ///
+        /// On success a `MinterTransferred` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotMinter` error if the caller is not the minter.
+        #[ink(message)]
+        pub fn renounce_minter_role(&mut self) -> Result<()> {
+            self.ensure_minter()?;
+            self.set_minter(None);
+            Ok(())
+        }
 
-        fn assert_transfer_event(
-            event: &ink_env::test::EmittedEvent,
-            expected_from: Option<AccountId>,
-            expected_to: Option<AccountId>,
-            expected_value: Balance,
-        ) {
-            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+        /// Stops transfers and approvals until the token is unpaused.
+        ///
+        /// On success a `Paused` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotPauser` error if the caller is not the pauser.
+        ///
+        /// Returns `Paused` error if the token is already paused.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<()> {
+            self.ensure_pauser()?;
+            self.ensure_not_paused()?;
+            *self.paused = true;
+            self.env().emit_event(Paused {
+                account: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// Resumes transfers and approvals.
+        ///
+        /// On success an `Unpaused` event is emitted. Unpausing a token which is
+        /// not paused does nothing.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotPauser` error if the caller is not the pauser.
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<()> {
+            self.ensure_pauser()?;
+            if self.paused() {
+                *self.paused = false;
+                self.env().emit_event(Unpaused {
+                    account: self.env().caller(),
+                });
+            }
+            Ok(())
+        }
+
+        /// Takes a snapshot of all balances and the total supply and returns its id.
+        ///
+        /// Snapshot ids start at `1` and increase monotonically.
+        ///
+        /// A `Snapshot` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotMinter` error if the caller is not the minter.
+        #[ink(message)]
+        pub fn snapshot(&mut self) -> Result<SnapshotId> {
+            self.ensure_minter()?;
+            let id = self.current_snapshot_id() + 1;
+            *self.current_snapshot_id = id;
+            self.env().emit_event(Snapshot { id });
+            Ok(id)
+        }
+
+        /// Returns the id of the most recent snapshot.
+        ///
+        /// Returns `0` if no snapshot was taken yet.
+        #[ink(message)]
+        pub fn current_snapshot_id(&self) -> SnapshotId {
+            *self.current_snapshot_id
+        }
+
+        /// Returns the balance of `owner` at the time snapshot `id` was taken.
+        ///
+        /// Returns `None` if the snapshot `id` does not exist.
+        #[ink(message)]
+        pub fn balance_of_at(&self, owner: AccountId, id: SnapshotId) -> Option<Balance> {
+            if !self.snapshot_exists(id) {
+                return None;
+            }
+            let count = self
+                .account_checkpoint_counts
+                .get(&owner)
+                .copied()
+                .unwrap_or(0);
+            Some(
+                Self::value_at(count, |index| self.account_checkpoints[&(owner, index)], id)
+                    .unwrap_or_else(|| self.balance_of(owner)),
+            )
+        }
+
+        /// Returns the total supply at the time snapshot `id` was taken.
+        ///
+        /// Returns `None` if the snapshot `id` does not exist.
+        #[ink(message)]
+        pub fn total_supply_at(&self, id: SnapshotId) -> Option<Balance> {
+            if !self.snapshot_exists(id) {
+                return None;
+            }
+            Some(
+                Self::value_at(
+                    *self.total_supply_checkpoint_count,
+                    |index| self.total_supply_checkpoints[&index],
+                    id,
+                )
+                .unwrap_or_else(|| self.total_supply()),
+            )
+        }
+
+        fn snapshot_exists(&self, id: SnapshotId) -> bool {
+            id > 0 && id <= self.current_snapshot_id()
+        }
+
+        /// Returns the value of the first of the `count` checkpoints taken at or
+        /// after snapshot `id`.
+        ///
+        /// Returns `None` if the value did not change since snapshot `id`, in
+        /// which case the current value applies.
+        fn value_at<F>(count: u32, checkpoint: F, id: SnapshotId) -> Option<Balance>
+        where
+            F: Fn(u32) -> (SnapshotId, Balance),
+        {
+            let (mut low, mut high) = (0, count);
+            while low < high {
+                let middle = low + (high - low) / 2;
+                if checkpoint(middle).0 < id {
+                    low = middle + 1;
+                } else {
+                    high = middle;
+                }
+            }
+            if low < count {
+                Some(checkpoint(low).1)
+            } else {
+                None
+            }
+        }
+
+        /// Records the current balance of `owner` before it changes.
+        fn update_account_snapshot(&mut self, owner: AccountId) {
+            let id = self.current_snapshot_id();
+            if id == 0 {
+                return;
+            }
+            let count = self
+                .account_checkpoint_counts
+                .get(&owner)
+                .copied()
+                .unwrap_or(0);
+            if count > 0 && self.account_checkpoints[&(owner, count - 1)].0 >= id {
+                return;
+            }
+            let balance = self.balance_of(owner);
+            self.account_checkpoints
+                .insert((owner, count), (id, balance));
+            self.account_checkpoint_counts.insert(owner, count + 1);
+        }
+
+        /// Records the current total supply before it changes.
+        fn update_total_supply_snapshot(&mut self) {
+            let id = self.current_snapshot_id();
+            if id == 0 {
+                return;
+            }
+            let count = *self.total_supply_checkpoint_count;
+            if count > 0 && self.total_supply_checkpoints[&(count - 1)].0 >= id {
+                return;
+            }
+            let total_supply = self.total_supply();
+            self.total_supply_checkpoints
+                .insert(count, (id, total_supply));
+            *self.total_supply_checkpoint_count = count + 1;
+        }
+
+        /// Calls `on_tokens_received` on `to` with the details of a transfer.
+        ///
+        /// Accounts which are no contracts or do not implement the message reject
+        /// the transfer, as the sender expected them to handle `data`.
+        #[cfg(not(test))]
+        fn notify_receiver(
+            &self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<()> {
+            let result = build_call::<Environment>()
+                .callee(to)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ON_TOKENS_RECEIVED_SELECTOR))
+                        .push_arg(self.env().caller())
+                        .push_arg(from)
+                        .push_arg(value)
+                        .push_arg(data),
+                )
+                .returns::<ReturnType<core::result::Result<(), String>>>()
+                .fire();
+            match result {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(reason)) => Err(Error::SafeTransferCheckFailed(reason)),
+                Err(_) => Err(Error::SafeTransferCheckFailed(String::from(
+                    "receiver call failed",
+                ))),
+            }
+        }
+
+        /// Calls `on_companion_transfer` on the controller, if any, with the details
+        /// of a transfer.
+        #[cfg(not(test))]
+        fn notify_controller(&self, from: AccountId, to: AccountId, value: Balance) -> Result<()> {
+            let controller = match self.controller() {
+                Some(controller) => controller,
+                None => return Ok(()),
+            };
+            let result = build_call::<Environment>()
+                .callee(controller)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ON_COMPANION_TRANSFER_SELECTOR))
+                        .push_arg(from)
+                        .push_arg(to)
+                        .push_arg(value),
+                )
+                .returns::<ReturnType<core::result::Result<(), String>>>()
+                .fire();
+            match result {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(reason)) => Err(Error::Custom(reason)),
+                Err(_) => Err(Error::Custom(String::from("controller call failed"))),
+            }
+        }
+
+        /// The off-chain environment cannot call contracts, so the controller is
+        /// never notified in unit tests.
+        #[cfg(test)]
+        fn notify_controller(
+            &self,
+            _from: AccountId,
+            _to: AccountId,
+            _value: Balance,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        /// The off-chain environment cannot call contracts, so unit tests stand in for
+        /// the receiver with `RECEIVER_HOOK`, run once on the next notification.
+        #[cfg(test)]
+        fn notify_receiver(
+            &mut self,
+            _from: AccountId,
+            _to: AccountId,
+            _value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<()> {
+            if let Some(hook) = RECEIVER_HOOK.with(|hook| hook.borrow_mut().take()) {
+                hook(self);
+            }
+            Ok(())
+        }
+
+        fn ensure_can_transfer(
+            &self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            if from == AccountId::from([0x0; 32]) {
+                return Err(Error::ZeroSenderAddress);
+            }
+            if to == AccountId::from([0x0; 32]) {
+                return Err(Error::ZeroRecipientAddress);
+            }
+            if self.balance_of(from) < value {
+                return Err(Error::InsufficientBalance);
+            }
+            Ok(())
+        }
+
+        fn ensure_pauser(&self) -> Result<()> {
+            if self.pauser() != Some(self.env().caller()) {
+                return Err(Error::NotPauser);
+            }
+            Ok(())
+        }
+
+        fn ensure_transferable(&self) -> Result<()> {
+            if !self.transferable() {
+                return Err(Error::NonTransferable);
+            }
+            Ok(())
+        }
+
+        fn ensure_not_paused(&self) -> Result<()> {
+            if self.paused() {
+                return Err(Error::Paused);
+            }
+            Ok(())
+        }
+
+        fn ensure_minter(&self) -> Result<()> {
+            if self.minter() != Some(self.env().caller()) {
+                return Err(Error::NotMinter);
+            }
+            Ok(())
+        }
+
+        fn set_minter(&mut self, new_minter: Option<AccountId>) {
+            let previous_minter = self.minter();
+            *self.minter = new_minter;
+            self.env().emit_event(MinterTransferred {
+                previous_minter,
+                new_minter,
+            });
+        }
+
+        /**
+         * @dev Increase the amount of tokens that an owner allowed to a spender.
+         * approve should be called when allowed_[_spender] == 0. To increment
+         * allowed value is better to use this function to avoid 2 calls (and wait until
+         * the first transaction is mined)
+         * From MonolithDAO Token.sol
+         * Emits an Approval event.
+         * @param spender The address which will spend the funds.
+         * @param addedValue The amount of tokens to increase the allowance by.
+         * Returns an `AllowanceOverflow` error if the new allowance overflows.
+         */
+        #[ink(message)]
+        #[ink(selector = "0x96d6b57a")]
+        pub fn increase_allowance(
+            &mut self,
+            spender: AccountId,
+            added_value: Balance,
+        ) -> Result<()> {
+            let owner = self.env().caller();
+            let new_value = self
+                .allowance(owner, spender)
+                .checked_add(added_value)
+                .ok_or(Error::AllowanceOverflow)?;
+            self.approve(spender, new_value)
+        }
+
+        /**
+         * @dev Decrease the amount of tokens that an owner allowed to a spender.
+         * approve should be called when allowed_[_spender] == 0. To decrement
+         * allowed value is better to use this function to avoid 2 calls (and wait until
+         * the first transaction is mined)
+         * From MonolithDAO Token.sol
+         * Emits an Approval event.
+         * @param spender The address which will spend the funds.
+         * @param subtractedValue The amount of tokens to decrease the allowance by.
+         * Returns an `AllowanceUnderflow` error if the allowance is smaller than
+         * subtractedValue.
+         */
+        #[ink(message)]
+        #[ink(selector = "0xfecb57d5")]
+        pub fn decrease_allowance(
+            &mut self,
+            spender: AccountId,
+            subtracted_value: Balance,
+        ) -> Result<()> {
+            // _allowed[msg.sender][spender].sub(subtracted_value),
+            let owner = self.env().caller();
+            let new_value = self
+                .allowance(owner, spender)
+                .checked_sub(subtracted_value)
+                .ok_or(Error::AllowanceUnderflow)?;
+            self.approve(spender, new_value)
+        }
+    }
+
+    /// Unit tests.
+    #[cfg(test)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+        use ink_env::{
+            hash::{Blake2x256, CryptoHash, HashOutput},
+            Clear,
+        };
+
+        type Event = <Erc20 as ::ink_lang::BaseEvent>::Type;
+
+        use ink_lang as ink;
+
+        fn assert_transfer_event(
+            event: &ink_env::test::EmittedEvent,
+            expected_from: Option<AccountId>,
+            expected_to: Option<AccountId>,
+            expected_value: Balance,
+        ) {
+            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
                 .expect("encountered invalid contract event data buffer");
             if let Event::Transfer(Transfer { from, to, value }) = decoded_event {
                 assert_eq!(from, expected_from, "encountered invalid Transfer.from");
@@ -330,6 +1220,45 @@ mod erc20 {
             }
         }
 
+        fn assert_approval_event(
+            event: &ink_env::test::EmittedEvent,
+            expected_owner: AccountId,
+            expected_spender: AccountId,
+            expected_value: Balance,
+        ) {
+            let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+                .expect("encountered invalid contract event data buffer");
+            if let Event::Approval(Approval {
+                owner,
+                spender,
+                value,
+            }) = decoded_event
+            {
+                assert_eq!(owner, expected_owner, "encountered invalid Approval.owner");
+                assert_eq!(
+                    spender, expected_spender,
+                    "encountered invalid Approval.spender"
+                );
+                assert_eq!(value, expected_value, "encountered invalid Approval.value");
+            } else {
+                panic!("encountered unexpected event kind: expected an Approval event")
+            }
+        }
+
+        /// Sets `caller` as the caller of the following calls.
+        fn set_caller(caller: AccountId) {
+            // Get contract address.
+            let callee =
+                ink_env::account_id::<ink_env::DefaultEnvironment>().unwrap_or([0x0; 32].into());
+            // Create call.
+            let mut data = ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])); // balance_of
+            data.push_arg(&caller);
+            // Push the new execution context to set `caller` as caller.
+            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
+                caller, callee, 1000000, 1000000, data,
+            );
+        }
+
         /// The default constructor does its job.
         #[ink::test]
         fn new_works() {
@@ -348,6 +1277,40 @@ mod erc20 {
             );
         }
 
+        /// The token has no metadata unless it was given on construction.
+        #[ink::test]
+        fn new_has_no_metadata() {
+            let erc20 = Erc20::new(100);
+            assert_eq!(erc20.token_name(), None);
+            assert_eq!(erc20.token_symbol(), None);
+            assert_eq!(erc20.token_decimals(), 0);
+        }
+
+        /// The metadata given on construction is stored.
+        #[ink::test]
+        fn new_with_metadata_works() {
+            let erc20 = Erc20::new_with_metadata(
+                100,
+                Some(String::from("Moloch Token")),
+                Some(String::from("MOL")),
+                18,
+            );
+            assert_eq!(erc20.token_name(), Some(String::from("Moloch Token")));
+            assert_eq!(erc20.token_symbol(), Some(String::from("MOL")));
+            assert_eq!(erc20.token_decimals(), 18);
+            assert_eq!(erc20.total_supply(), 100);
+
+            // Transfer event triggered during initial construction.
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(1, emitted_events.len());
+            assert_transfer_event(
+                &emitted_events[0],
+                None,
+                Some(AccountId::from([0x01; 32])),
+                100,
+            );
+        }
+
         /// The total supply was applied.
         #[ink::test]
         fn total_supply_works() {
@@ -396,7 +1359,7 @@ mod erc20 {
 
             assert_eq!(erc20.balance_of(accounts.bob), 0);
             // Alice transfers 10 tokens to Bob.
-            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 10, Vec::new()), Ok(()));
             // Bob owns 10 tokens.
             assert_eq!(erc20.balance_of(accounts.bob), 10);
 
@@ -443,7 +1406,7 @@ mod erc20 {
 
             // Bob fails to transfers 10 tokens to Eve.
             assert_eq!(
-                erc20.transfer(accounts.eve, 10),
+                erc20.transfer(accounts.eve, 10, Vec::new()),
                 Err(Error::InsufficientBalance)
             );
             // Alice owns all the tokens.
@@ -472,7 +1435,7 @@ mod erc20 {
 
             // Bob fails to transfer tokens owned by Alice.
             assert_eq!(
-                erc20.transfer_from(accounts.alice, accounts.eve, 10),
+                erc20.transfer_from(accounts.alice, accounts.eve, 10, Vec::new()),
                 Err(Error::InsufficientAllowance)
             );
             // Alice approves Bob for token transfers on her behalf.
@@ -498,7 +1461,7 @@ mod erc20 {
 
             // Bob transfers tokens from Alice to Eve.
             assert_eq!(
-                erc20.transfer_from(accounts.alice, accounts.eve, 10),
+                erc20.transfer_from(accounts.alice, accounts.eve, 10, Vec::new()),
                 Ok(())
             );
             // Eve owns tokens.
@@ -506,7 +1469,7 @@ mod erc20 {
 
             // Check all transfer events that happened during the previous calls:
             let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
-            assert_eq!(emitted_events.len(), 3);
+            assert_eq!(emitted_events.len(), 4);
             assert_transfer_event(
                 &emitted_events[0],
                 None,
@@ -514,8 +1477,10 @@ mod erc20 {
                 100,
             );
             // The second event `emitted_events[1]` is an Approve event that we skip checking.
+            // The third event is an Approval event with the remaining allowance.
+            assert_approval_event(&emitted_events[2], accounts.alice, accounts.bob, 0);
             assert_transfer_event(
-                &emitted_events[2],
+                &emitted_events[3],
                 Some(AccountId::from([0x01; 32])),
                 Some(AccountId::from([0x05; 32])),
                 10,
@@ -551,7 +1516,7 @@ mod erc20 {
             // Bob tries to transfer tokens from Alice to Eve.
             let emitted_events_before = ink_env::test::recorded_events().collect::<Vec<_>>();
             assert_eq!(
-                erc20.transfer_from(accounts.alice, accounts.eve, alice_balance + 1),
+                erc20.transfer_from(accounts.alice, accounts.eve, alice_balance + 1, Vec::new()),
                 Err(Error::InsufficientBalance)
             );
             // Allowance must have stayed the same
@@ -563,6 +1528,530 @@ mod erc20 {
             let emitted_events_after = ink_env::test::recorded_events().collect::<Vec<_>>();
             assert_eq!(emitted_events_before.len(), emitted_events_after.len());
         }
+
+        #[ink::test]
+        fn minter_is_the_creator() {
+            let erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+            assert_eq!(erc20.minter(), Some(accounts.alice));
+            assert_eq!(erc20.cap(), None);
+        }
+
+        #[ink::test]
+        fn mint_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            // Alice mints 10 tokens to Bob.
+            assert_eq!(erc20.mint(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.total_supply(), 110);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 2);
+            assert_transfer_event(&emitted_events[1], None, Some(accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn mint_requires_minter() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.mint(accounts.bob, 10), Err(Error::NotMinter));
+            assert_eq!(erc20.burn_from(accounts.alice, 10), Err(Error::NotMinter));
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.total_supply(), 100);
+        }
+
+        #[ink::test]
+        fn mint_respects_cap() {
+            let mut erc20 = Erc20::new_capped(100, 150, None, None, 0);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+            assert_eq!(erc20.cap(), Some(150));
+
+            assert_eq!(erc20.mint(accounts.bob, 51), Err(Error::CapExceeded));
+            assert_eq!(erc20.mint(accounts.bob, 50), Ok(()));
+            assert_eq!(erc20.mint(accounts.bob, 1), Err(Error::CapExceeded));
+            assert_eq!(erc20.total_supply(), 150);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "initial supply exceeds cap")]
+        fn new_capped_rejects_initial_supply_above_cap() {
+            let _erc20 = Erc20::new_capped(151, 150, None, None, 0);
+        }
+
+        #[ink::test]
+        fn burn_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.burn(101), Err(Error::InsufficientBalance));
+            assert_eq!(erc20.burn(10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 90);
+            assert_eq!(erc20.total_supply(), 90);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 2);
+            assert_transfer_event(&emitted_events[1], Some(accounts.alice), None, 10);
+        }
+
+        #[ink::test]
+        fn burn_from_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.transfer(accounts.bob, 10, Vec::new()), Ok(()));
+            // The minter burns Bob's tokens without any allowance.
+            assert_eq!(erc20.burn_from(accounts.bob, 4), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 6);
+            assert_eq!(erc20.total_supply(), 96);
+        }
+
+        #[ink::test]
+        fn transfer_minter_role_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.transfer_minter_role(accounts.bob), Ok(()));
+            assert_eq!(erc20.minter(), Some(accounts.bob));
+            // Alice is no longer the minter.
+            assert_eq!(erc20.mint(accounts.alice, 10), Err(Error::NotMinter));
+            assert_eq!(
+                erc20.transfer_minter_role(accounts.alice),
+                Err(Error::NotMinter)
+            );
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.mint(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn renounce_minter_role_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.renounce_minter_role(), Ok(()));
+            assert_eq!(erc20.minter(), None);
+            assert_eq!(erc20.mint(accounts.alice, 10), Err(Error::NotMinter));
+            assert_eq!(erc20.burn(10), Err(Error::NotMinter));
+        }
+
+        #[ink::test]
+        fn pause_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+            assert_eq!(erc20.pauser(), Some(accounts.alice));
+            assert!(!erc20.paused());
+
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.pause(), Ok(()));
+            assert!(erc20.paused());
+            assert_eq!(erc20.pause(), Err(Error::Paused));
+
+            // Transfers and approvals are blocked.
+            assert_eq!(
+                erc20.transfer(accounts.bob, 10, Vec::new()),
+                Err(Error::Paused)
+            );
+            assert_eq!(erc20.approve(accounts.bob, 20), Err(Error::Paused));
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 10, Vec::new()),
+                Err(Error::Paused)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn unpause_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.pause(), Ok(()));
+            assert_eq!(erc20.unpause(), Ok(()));
+            assert!(!erc20.paused());
+            assert_eq!(erc20.transfer(accounts.bob, 10, Vec::new()), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+
+            // Constructor, Paused, Unpaused and Transfer events.
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 4);
+        }
+
+        #[ink::test]
+        fn pause_requires_pauser() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.pause(), Err(Error::NotPauser));
+            assert!(!erc20.paused());
+            assert_eq!(erc20.unpause(), Err(Error::NotPauser));
+        }
+
+        #[ink::test]
+        fn snapshot_works() {
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(erc20.current_snapshot_id(), 0);
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert_eq!(erc20.snapshot(), Ok(2));
+            assert_eq!(erc20.current_snapshot_id(), 2);
+
+            // Constructor and two Snapshot events.
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+        }
+
+        #[ink::test]
+        fn snapshot_by_non_minter_fails() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            set_caller(accounts.bob);
+            assert_eq!(erc20.snapshot(), Err(Error::NotMinter));
+            assert_eq!(erc20.current_snapshot_id(), 0);
+        }
+
+        #[ink::test]
+        fn balance_of_at_nonexistent_snapshot_fails() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+            assert_eq!(erc20.balance_of_at(accounts.alice, 0), None);
+            assert_eq!(erc20.balance_of_at(accounts.alice, 1), None);
+            assert_eq!(erc20.total_supply_at(1), None);
+
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert_eq!(erc20.balance_of_at(accounts.alice, 1), Some(100));
+            assert_eq!(erc20.total_supply_at(1), Some(100));
+            assert_eq!(erc20.total_supply_at(2), None);
+        }
+
+        #[ink::test]
+        fn transfers_after_snapshot_keep_historical_balances() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            let first = erc20.snapshot().unwrap();
+            assert_eq!(erc20.transfer(accounts.bob, 10, Vec::new()), Ok(()));
+            assert_eq!(erc20.transfer(accounts.bob, 20, Vec::new()), Ok(()));
+            let second = erc20.snapshot().unwrap();
+            assert_eq!(erc20.transfer(accounts.charlie, 30, Vec::new()), Ok(()));
+
+            assert_eq!(erc20.balance_of_at(accounts.alice, first), Some(100));
+            assert_eq!(erc20.balance_of_at(accounts.bob, first), Some(0));
+            assert_eq!(erc20.balance_of_at(accounts.charlie, first), Some(0));
+            assert_eq!(erc20.balance_of_at(accounts.alice, second), Some(70));
+            assert_eq!(erc20.balance_of_at(accounts.bob, second), Some(30));
+            assert_eq!(erc20.balance_of_at(accounts.charlie, second), Some(0));
+
+            // Without a later snapshot the current balance applies.
+            let third = erc20.snapshot().unwrap();
+            assert_eq!(erc20.balance_of_at(accounts.alice, third), Some(40));
+            assert_eq!(erc20.balance_of_at(accounts.charlie, third), Some(30));
+        }
+
+        #[ink::test]
+        fn mint_and_burn_after_snapshot_keep_historical_total_supply() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            let first = erc20.snapshot().unwrap();
+            assert_eq!(erc20.mint(accounts.bob, 50), Ok(()));
+            let second = erc20.snapshot().unwrap();
+            assert_eq!(erc20.burn(30), Ok(()));
+
+            assert_eq!(erc20.total_supply_at(first), Some(100));
+            assert_eq!(erc20.total_supply_at(second), Some(150));
+            assert_eq!(erc20.total_supply(), 120);
+            assert_eq!(erc20.balance_of_at(accounts.bob, first), Some(0));
+            assert_eq!(erc20.balance_of_at(accounts.bob, second), Some(50));
+            assert_eq!(erc20.balance_of_at(accounts.alice, second), Some(100));
+            assert_eq!(erc20.balance_of(accounts.alice), 70);
+        }
+
+        #[ink::test]
+        fn transfer_to_zero_address_fails() {
+            let mut erc20 = Erc20::new(100);
+            assert_eq!(
+                erc20.transfer(AccountId::from([0x0; 32]), 10, Vec::new()),
+                Err(Error::ZeroRecipientAddress)
+            );
+            assert_eq!(erc20.total_supply(), 100);
+        }
+
+        #[ink::test]
+        fn increase_allowance_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.increase_allowance(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.increase_allowance(accounts.bob, 5), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 15);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+            assert_approval_event(&emitted_events[2], accounts.alice, accounts.bob, 15);
+        }
+
+        #[ink::test]
+        fn increase_allowance_overflow_fails() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.approve(accounts.bob, Balance::MAX), Ok(()));
+            assert_eq!(
+                erc20.increase_allowance(accounts.bob, 1),
+                Err(Error::AllowanceOverflow)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), Balance::MAX);
+        }
+
+        #[ink::test]
+        fn decrease_allowance_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.decrease_allowance(accounts.bob, 4), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 6);
+            assert_eq!(erc20.decrease_allowance(accounts.bob, 6), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn decrease_allowance_underflow_fails() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(
+                erc20.decrease_allowance(accounts.bob, 1),
+                Err(Error::AllowanceUnderflow)
+            );
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+            assert_eq!(
+                erc20.decrease_allowance(accounts.bob, 11),
+                Err(Error::AllowanceUnderflow)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn transfer_from_emits_remaining_allowance() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.eve, 3, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 7);
+
+            // Constructor, Approval, Approval and Transfer events.
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 4);
+            assert_approval_event(&emitted_events[2], accounts.alice, accounts.bob, 7);
+        }
+
+        #[ink::test]
+        fn transfer_from_cannot_be_reentered_with_the_spent_allowance() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.approve(accounts.bob, 10), Ok(()));
+            set_caller(accounts.bob);
+            // Bob's contract receives the tokens and spends the allowance again.
+            RECEIVER_HOOK.with(|hook| {
+                *hook.borrow_mut() = Some(|erc20: &mut Erc20| {
+                    let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                        .expect("Cannot get accounts");
+                    assert_eq!(
+                        erc20.transfer_from(accounts.alice, accounts.bob, 10, vec![1]),
+                        Err(Error::InsufficientAllowance)
+                    );
+                })
+            });
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 10, vec![1]),
+                Ok(())
+            );
+
+            assert!(RECEIVER_HOOK.with(|hook| hook.borrow().is_none()));
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.balance_of(accounts.alice), 90);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn batch_transfer_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(
+                erc20.batch_transfer(vec![(accounts.bob, 10), (accounts.charlie, 20)]),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 70);
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.balance_of(accounts.charlie), 20);
+
+            // Constructor and one Transfer event per recipient.
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+            assert_transfer_event(
+                &emitted_events[1],
+                Some(accounts.alice),
+                Some(accounts.bob),
+                10,
+            );
+            assert_transfer_event(
+                &emitted_events[2],
+                Some(accounts.alice),
+                Some(accounts.charlie),
+                20,
+            );
+        }
+
+        #[ink::test]
+        fn batch_transfer_is_atomic() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(
+                erc20.batch_transfer(vec![(accounts.bob, 60), (accounts.charlie, 41)]),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(
+                erc20.batch_transfer(vec![(accounts.bob, 10), (AccountId::from([0x0; 32]), 10)]),
+                Err(Error::ZeroRecipientAddress)
+            );
+            assert_eq!(
+                erc20.batch_transfer(vec![(accounts.bob, 1), (accounts.charlie, Balance::MAX)]),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 100);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.balance_of(accounts.charlie), 0);
+            assert_eq!(ink_env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn batch_transfer_rejects_too_large_batch() {
+            let mut erc20 = Erc20::new(1000);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            let transfers = vec![(accounts.bob, 1); MAX_BATCH_SIZE + 1];
+            assert_eq!(erc20.batch_transfer(transfers), Err(Error::BatchTooLarge));
+            let transfers = vec![(accounts.bob, 1); MAX_BATCH_SIZE];
+            assert_eq!(erc20.batch_transfer(transfers), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), MAX_BATCH_SIZE as Balance);
+        }
+
+        #[ink::test]
+        fn batch_transfer_from_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+
+            assert_eq!(erc20.approve(accounts.bob, 25), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.batch_transfer_from(
+                    accounts.alice,
+                    vec![(accounts.charlie, 10), (accounts.eve, 16)]
+                ),
+                Err(Error::InsufficientAllowance)
+            );
+            assert_eq!(
+                erc20.batch_transfer_from(
+                    accounts.alice,
+                    vec![(accounts.charlie, 10), (accounts.eve, 15)]
+                ),
+                Ok(())
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 75);
+            assert_eq!(erc20.balance_of(accounts.charlie), 10);
+            assert_eq!(erc20.balance_of(accounts.eve), 15);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 0);
+
+            // Constructor, Approval, two Transfer and Approval events.
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 5);
+            assert_approval_event(&emitted_events[4], accounts.alice, accounts.bob, 0);
+        }
+
+        #[ink::test]
+        fn new_companion_works() {
+            let erc20 = Erc20::new_companion(Some(String::from("Shares")), None, false);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+            assert_eq!(erc20.total_supply(), 0);
+            assert_eq!(erc20.token_name(), Some(String::from("Shares")));
+            assert_eq!(erc20.minter(), Some(accounts.alice));
+            assert!(!erc20.transferable());
+            assert_eq!(erc20.controller(), None);
+
+            let erc20 = Erc20::new_companion(None, None, true);
+            assert!(erc20.transferable());
+            assert_eq!(erc20.controller(), Some(accounts.alice));
+        }
+
+        #[ink::test]
+        fn non_transferable_token_cannot_be_transferred() {
+            let mut erc20 = Erc20::new_companion(None, None, false);
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("Cannot get accounts");
+            assert_eq!(erc20.mint(accounts.bob, 10), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                erc20.transfer(accounts.charlie, 5, Vec::new()),
+                Err(Error::NonTransferable)
+            );
+            assert_eq!(
+                erc20.batch_transfer(vec![(accounts.charlie, 5)]),
+                Err(Error::NonTransferable)
+            );
+            assert_eq!(erc20.approve(accounts.alice, 5), Ok(()));
+            set_caller(accounts.alice);
+            assert_eq!(
+                erc20.transfer_from(accounts.bob, accounts.charlie, 5, Vec::new()),
+                Err(Error::NonTransferable)
+            );
+
+            // The minter still burns tokens.
+            assert_eq!(erc20.burn_from(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+        }
     }
 
     /// For calculating the event topic hash.
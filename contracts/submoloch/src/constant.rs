@@ -10,7 +10,19 @@ pub const MAX_GRACE_PERIOD_LENGTH: u128 = 1000000000000000000;
 pub const MAX_DILUTION_BOUND: u128 = 1000000000000000000;
 /// maximum number of shares that can be minted
 pub const MAX_NUMBER_OF_SHARES_AND_LOOT: u128 = 1000000000000000000;
+/// maximum length of the cliff and of the vesting periods of a vesting schedule
+pub const MAX_VESTING_PERIOD_LENGTH: u128 = 1000000000000000000;
 /// maximum number of whitelisted tokens
 pub const MAX_TOKEN_WHITELIST_COUNT: u128 = 400;
 /// maximum number of tokens with non-zero balance in guildbank
-pub const MAX_TOKEN_GUILDBANK_COUNT: u128 = 200;
\ No newline at end of file
+pub const MAX_TOKEN_GUILDBANK_COUNT: u128 = 200;
+/// maximum number of members delegating their votes to the same member
+pub const MAX_VOTE_DELEGATORS: u128 = 100;
+/// maximum number of conviction proposals a member can have shares staked on at once
+pub const MAX_STAKED_PROPOSALS: u128 = 50;
+/// maximum number of options of a multi-option proposal
+pub const MAX_PROPOSAL_OPTIONS: u128 = 10;
+/// maximum number of storage entries the state can span to be exported in one call
+pub const MAX_EXPORT_ENTRIES: u128 = 5000;
+/// minimum percentage of the total voting weight needed to pause or unpause the Moloch
+pub const PAUSE_SUPERMAJORITY_PERCENTAGE: u128 = 66;
\ No newline at end of file
@@ -0,0 +1,169 @@
+//! CONVICTION VOTING
+//! Members stake shares on funding proposals, the conviction of a proposal grows with the
+//! shares staked on it every period and it can be executed once it crosses its threshold.
+
+use ink_env::AccountId;
+
+/// share of conviction kept from one period to the next (alpha = 9/10)
+pub const DECAY_NUMERATOR: u128 = 9;
+pub const DECAY_DENOMINATOR: u128 = 10;
+/// conviction built up by a share staked for one period, large enough to not be lost to rounding
+pub const CONVICTION_PER_SHARE: u128 = 1000;
+/// the most conviction a share can build up, 1 / (1 - alpha) periods of staking
+pub const MAX_CONVICTION_PER_SHARE: u128 = 10 * CONVICTION_PER_SHARE;
+/// number of periods after which conviction is considered settled, alpha^64 is below 0.2%
+pub const MAX_ACCRUAL_PERIODS: u128 = 64;
+/// maximum percentage of the guild bank balance of a token a single proposal can request
+pub const SPENDING_LIMIT_PERCENTAGE: u128 = 20;
+/// percentage of the maximum conviction of all shares needed by a proposal requesting nothing
+pub const MIN_THRESHOLD_PERCENTAGE: u128 = 20;
+
+/// Defines ConvictionProposal, a funding request outside of the proposal queue.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct ConvictionProposal {
+    /// the account that submitted the proposal
+    pub proposer: AccountId,
+    /// the account paid once the proposal is executed
+    pub applicant: AccountId,
+    /// amount of tokens requested as payment
+    pub payment_requested: u128,
+    /// payment token contract reference
+    pub payment_token: AccountId,
+    /// the shares currently staked on the proposal
+    pub staked: u128,
+    /// the conviction as of `last_period`
+    pub conviction: u128,
+    /// the period in which the conviction was last updated
+    pub last_period: u128,
+    /// whether the payment has been made
+    pub executed: bool,
+    /// blake2x256 hash of the proposal details, which are only emitted in full
+    pub details: [u8; 32],
+}
+
+impl ConvictionProposal {
+    /// Returns the conviction of the proposal in the given period.
+    pub fn conviction_at(&self, period: u128) -> u128 {
+        accrue(
+            self.conviction,
+            self.staked,
+            period.saturating_sub(self.last_period),
+        )
+    }
+}
+
+/// Returns the conviction after `periods` periods with `staked` shares staked throughout.
+pub fn accrue(conviction: u128, staked: u128, periods: u128) -> u128 {
+    let mut conviction = conviction;
+    for _ in 0..periods.min(MAX_ACCRUAL_PERIODS) {
+        conviction =
+            conviction * DECAY_NUMERATOR / DECAY_DENOMINATOR + staked * CONVICTION_PER_SHARE;
+    }
+    conviction
+}
+
+/// Returns the conviction needed to pay `requested` out of the `funds` held by the guild bank,
+/// none if the request exceeds the spending limit.
+///
+/// The threshold grows with the square of the part of the spending limit left over by the
+/// request, it saturates instead of overflowing.
+pub fn threshold(requested: u128, funds: u128, total_shares: u128) -> Option<u128> {
+    let limit =
+        funds / 100 * SPENDING_LIMIT_PERCENTAGE + funds % 100 * SPENDING_LIMIT_PERCENTAGE / 100;
+    if requested >= limit {
+        return None;
+    }
+    let remaining = limit - requested;
+    let base =
+        total_shares.saturating_mul(MAX_CONVICTION_PER_SHARE * MIN_THRESHOLD_PERCENTAGE) / 100;
+    Some((base.saturating_mul(limit) / remaining).saturating_mul(limit) / remaining)
+}
+
+pub type ConvictionProposalId = u128;
+pub type ConvictionProposals =
+    ink_storage::collections::HashMap<ConvictionProposalId, ConvictionProposal>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accrue_adds_stake_every_period() {
+        assert_eq!(accrue(0, 100, 0), 0);
+        assert_eq!(accrue(0, 100, 1), 100 * CONVICTION_PER_SHARE);
+        assert_eq!(accrue(0, 100, 2), 190 * CONVICTION_PER_SHARE);
+        assert_eq!(
+            accrue(190 * CONVICTION_PER_SHARE, 100, 1),
+            271 * CONVICTION_PER_SHARE
+        );
+    }
+
+    #[test]
+    fn accrue_grows_with_a_single_share() {
+        assert!(accrue(0, 1, 2) > accrue(0, 1, 1));
+    }
+
+    #[test]
+    fn accrue_decays_without_stake() {
+        assert_eq!(accrue(1000, 0, 1), 900);
+        assert_eq!(accrue(1000, 0, MAX_ACCRUAL_PERIODS), 0);
+    }
+
+    #[test]
+    fn accrue_settles_below_max_conviction() {
+        let settled = accrue(0, 100, u128::MAX);
+        assert!(settled <= 100 * MAX_CONVICTION_PER_SHARE);
+        assert!(settled > 100 * MAX_CONVICTION_PER_SHARE * 98 / 100);
+    }
+
+    #[test]
+    fn threshold_rejects_requests_above_spending_limit() {
+        assert_eq!(threshold(200, 1000, 100), None);
+        assert_eq!(threshold(1, 0, 100), None);
+        assert!(threshold(199, 1000, 100).is_some());
+    }
+
+    #[test]
+    fn threshold_grows_with_requested_amount() {
+        assert_eq!(threshold(0, 1000, 100), Some(200 * CONVICTION_PER_SHARE));
+        assert_eq!(threshold(100, 1000, 100), Some(800 * CONVICTION_PER_SHARE));
+        assert!(threshold(150, 1000, 100) > threshold(100, 1000, 100));
+    }
+
+    #[test]
+    fn threshold_saturates() {
+        assert!(threshold(u128::MAX / 100, u128::MAX, u128::MAX / 10).is_some());
+    }
+
+    #[test]
+    fn staking_half_the_shares_passes_small_requests() {
+        let proposal = ConvictionProposal {
+            proposer: AccountId::from([0x01; 32]),
+            applicant: AccountId::from([0x01; 32]),
+            payment_requested: 50,
+            payment_token: AccountId::from([0x02; 32]),
+            staked: 50,
+            conviction: 0,
+            last_period: 3,
+            executed: false,
+            details: [0; 32],
+        };
+        let needed = threshold(proposal.payment_requested, 1000, 100).unwrap();
+        assert!(proposal.conviction_at(4) < needed);
+        assert!(proposal.conviction_at(3 + MAX_ACCRUAL_PERIODS) >= needed);
+    }
+}
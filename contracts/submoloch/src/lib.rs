@@ -2,8 +2,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod constant;
+pub mod conviction;
 pub mod member;
+pub mod multi_option;
 pub mod proposal;
+pub mod state;
+pub mod token;
+pub mod vesting;
+pub mod voting;
 
 use ink_lang as ink;
 
@@ -23,20 +29,43 @@ mod utils {
             b
         }
     }
+
+    pub fn fair_share(balance: u128, shares: u128, total_shares: u128) -> u128 {
+        assert!(total_shares != 0);
+        if balance == 0 {
+            return 0;
+        }
+        match balance.checked_mul(shares) {
+            Some(prod) => prod / total_shares,
+            None => (balance / total_shares) * shares,
+        }
+    }
 }
 
 /// Define ink! contract.
 #[ink::contract]
 mod submoloch {
-    use ink_env::call::FromAccountId;
+    use ink_env::call::{
+        build_call, utils::ReturnType, ExecutionInput, FromAccountId, Selector, ToAccountId,
+    };
+    use ink_env::hash::Blake2x256;
     use ink_prelude::string::String;
     use ink_prelude::string::ToString;
     use ink_prelude::vec::Vec;
 
     use crate::constant;
+    use crate::conviction::{self, ConvictionProposal, ConvictionProposalId, ConvictionProposals};
     use crate::member::{Member, Members};
-    use crate::proposal::{Proposal, ProposalId, ProposalIndex, ProposalQueue, Proposals};
+    use crate::multi_option::{self, Ballot, MultiOption, Payment, TallyMethod};
+    use crate::proposal::{
+        Action, CallInput, CallOutput, Parameters, Proposal, ProposalId, ProposalIndex,
+        ProposalKind, ProposalKinds, ProposalParameters, ProposalQueue, Proposals, Vote,
+    };
+    use crate::state::{ProposalSnapshot, StateSnapshot};
+    use crate::token::TokenInfo;
     use crate::utils;
+    use crate::vesting::{VestingGrant, VestingSchedule};
+    use crate::voting::VotingMode;
     use erc20::Erc20;
 
     const GUILD: [u8; 32] = [
@@ -55,6 +84,14 @@ mod submoloch {
         0x98, 0x2d, 0x22, 0xd1, 0xbd, 0x46, 0x2e, 0x66, 0x10, 0x94, 0xa2, 0xb8, 0x57, 0x74, 0xa8,
         0x17, 0x4b,
     ];
+    // pseudo token id of the native currency, it can be whitelisted like any token
+    const NATIVE: [u8; 32] = [0xee; 32];
+    // selector of `export_state`, called by the successor to import the state
+    const EXPORT_STATE_SELECTOR: [u8; 4] = [0x3e, 0x6b, 0x0d, 0x52];
+    // selector of `successor`, called by the successor to check the export was approved to it
+    const SUCCESSOR_SELECTOR: [u8; 4] = [0x3e, 0x6b, 0x0d, 0x53];
+    // version of the storage layout, bumped whenever `Proposal`, `Member` or the storage fields change
+    const STORAGE_VERSION: u32 = 1;
 
     /* ----------------------------------------------------*
      * Event                                               *
@@ -74,6 +111,8 @@ mod submoloch {
         proposal_deposit: u128,
         dilution_bound: u128,
         processing_reward: u128,
+        quorum_percentage: u128,
+        supermajority_percentage: Option<u128>,
     }
     /// Defines SubmitProposal event.
     #[ink(event)]
@@ -172,6 +211,14 @@ mod submoloch {
         member_address: AccountId,
         new_delegate_key: AccountId,
     }
+    /// Defines DelegateVotes event.
+    #[ink(event)]
+    pub struct DelegateVotes {
+        #[ink(topic)]
+        delegator: AccountId,
+        from_delegate: Option<AccountId>,
+        to_delegate: Option<AccountId>,
+    }
     /// Defines Withdraw event.
     #[ink(event)]
     pub struct Withdraw {
@@ -180,36 +227,345 @@ mod submoloch {
         token: AccountId,
         amount: u128,
     }
+    /// Defines DepositSettled event.
+    #[ink(event)]
+    pub struct DepositSettled {
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        #[ink(topic)]
+        sponsor: AccountId,
+        processor: AccountId,
+        processing_reward: u128,
+        refunded: u128,
+    }
+
+    /// Defines ProcessActionProposal event.
+    #[ink(event)]
+    pub struct ProcessActionProposal {
+        #[ink(topic)]
+        proposal_index: ProposalIndex,
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        did_pass: bool,
+    }
+
+    /// Defines ExecuteAction event.
+    #[ink(event)]
+    pub struct ExecuteAction {
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        #[ink(topic)]
+        target: AccountId,
+        value: u128,
+        /// whether the call went through, a callee returning an error still succeeds
+        success: bool,
+        /// the raw SCALE-encoded value returned by the callee
+        output: Vec<u8>,
+    }
+
+    /// Defines ProcessParametersProposal event.
+    #[ink(event)]
+    pub struct ProcessParametersProposal {
+        #[ink(topic)]
+        proposal_index: ProposalIndex,
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        did_pass: bool,
+    }
+
+    /// Defines ParametersUpdated event.
+    #[ink(event)]
+    pub struct ParametersUpdated {
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        voting_period_length: u128,
+        grace_period_length: u128,
+        proposal_deposit: u128,
+        dilution_bound: u128,
+        processing_reward: u128,
+    }
+
+    /// Defines ProcessDewhitelistProposal event.
+    #[ink(event)]
+    pub struct ProcessDewhitelistProposal {
+        #[ink(topic)]
+        proposal_index: ProposalIndex,
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        did_pass: bool,
+    }
+
+    /// Defines ProcessExportProposal event.
+    #[ink(event)]
+    pub struct ProcessExportProposal {
+        #[ink(topic)]
+        proposal_index: ProposalIndex,
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        did_pass: bool,
+    }
+
+    /// Defines ImportState event.
+    #[ink(event)]
+    pub struct ImportState {
+        #[ink(topic)]
+        predecessor: AccountId,
+    }
+
+    /// Defines MigrateFunds event.
+    #[ink(event)]
+    pub struct MigrateFunds {
+        #[ink(topic)]
+        successor: AccountId,
+        #[ink(topic)]
+        token: AccountId,
+        amount: u128,
+    }
+
+    /// Defines SubmitConvictionProposal event.
+    #[ink(event)]
+    pub struct SubmitConvictionProposal {
+        #[ink(topic)]
+        conviction_proposal_id: ConvictionProposalId,
+        #[ink(topic)]
+        applicant: AccountId,
+        payment_requested: u128,
+        payment_token: AccountId,
+        details: String,
+    }
+
+    /// Defines StakeConviction event.
+    #[ink(event)]
+    pub struct StakeConviction {
+        #[ink(topic)]
+        conviction_proposal_id: ConvictionProposalId,
+        #[ink(topic)]
+        member_address: AccountId,
+        amount: u128,
+        staked: u128,
+    }
+
+    /// Defines UnstakeConviction event.
+    #[ink(event)]
+    pub struct UnstakeConviction {
+        #[ink(topic)]
+        conviction_proposal_id: ConvictionProposalId,
+        #[ink(topic)]
+        member_address: AccountId,
+        amount: u128,
+        staked: u128,
+    }
+
+    /// Defines ExecuteConvictionProposal event.
+    #[ink(event)]
+    pub struct ExecuteConvictionProposal {
+        #[ink(topic)]
+        conviction_proposal_id: ConvictionProposalId,
+        #[ink(topic)]
+        applicant: AccountId,
+        payment_requested: u128,
+        conviction: u128,
+    }
+
+    /// Defines SubmitRankedVote event.
+    #[ink(event)]
+    pub struct SubmitRankedVote {
+        proposal_id: ProposalId,
+        #[ink(topic)]
+        proposal_index: ProposalIndex,
+        delegate_key: AccountId,
+        #[ink(topic)]
+        member_address: AccountId,
+        ranking: Vec<u8>,
+    }
+
+    /// Defines OptionTally event.
+    #[ink(event)]
+    pub struct OptionTally {
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        option: u8,
+        weight: u128,
+    }
+
+    /// Defines ProcessMultiOptionProposal event.
+    #[ink(event)]
+    pub struct ProcessMultiOptionProposal {
+        #[ink(topic)]
+        proposal_index: ProposalIndex,
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        did_pass: bool,
+        winner: Option<u8>,
+    }
+
+    /// Defines VetoProposal event.
+    #[ink(event)]
+    pub struct VetoProposal {
+        #[ink(topic)]
+        proposal_index: ProposalIndex,
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        guardian: AccountId,
+    }
+
+    /// Defines ProcessRemoveGuardianProposal event.
+    #[ink(event)]
+    pub struct ProcessRemoveGuardianProposal {
+        #[ink(topic)]
+        proposal_index: ProposalIndex,
+        #[ink(topic)]
+        proposal_id: ProposalId,
+        did_pass: bool,
+    }
+
+    /// Defines GuardianRemoved event.
+    #[ink(event)]
+    pub struct GuardianRemoved {
+        #[ink(topic)]
+        guardian: AccountId,
+    }
+
+    /// Defines SubmitPauseVote event.
+    #[ink(event)]
+    pub struct SubmitPauseVote {
+        #[ink(topic)]
+        member_address: AccountId,
+        pause: bool,
+        weight: u128,
+    }
+
+    /// Defines Paused event.
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Defines Unpaused event.
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Defines ClaimVested event.
+    #[ink(event)]
+    pub struct ClaimVested {
+        #[ink(topic)]
+        member_address: AccountId,
+        shares: u128,
+        loot: u128,
+    }
+
+    /// Defines TokensReceived event.
+    #[ink(event)]
+    pub struct TokensReceived {
+        #[ink(topic)]
+        token: AccountId,
+        #[ink(topic)]
+        from: AccountId,
+        value: u128,
+        proposal_id: Option<ProposalId>,
+    }
 
     /// Defines the storage of this contract.
     /// #[cfg(not(feature = "ink-as-dependency"))]
     #[derive(Default)]
     #[ink(storage)]
     pub struct Submoloch {
+        /// version of the storage layout this instance was created with
+        storage_version: u32,
         members: Members,
         token_whitelist: ink_storage::collections::HashMap<AccountId, bool>,
         approved_tokens: ink_storage::collections::Vec<AccountId>,
+        /// tokens removed from the whitelist the guild bank still holds, claimable through ragequit
+        delisted_tokens: ink_storage::collections::Vec<AccountId>,
         period_duration: u16,
         voting_period_length: u128,
         grace_period_length: u128,
         proposal_deposit: u128,
         dilution_bound: u128,
         processing_reward: u128,
+        /// minimum percentage of total shares that must have voted for a proposal to pass
+        quorum_percentage: u128,
+        /// minimum percentage of yes votes among all votes for a proposal to pass, if any
+        supermajority_percentage: Option<u128>,
+        /// how the shares of a member weigh in a vote
+        voting_mode: VotingMode,
         proposed_to_whitelist: ink_storage::collections::HashMap<AccountId, bool>,
         proposed_to_kick: ink_storage::collections::HashMap<AccountId, bool>,
+        proposed_to_dewhitelist: ink_storage::collections::HashMap<AccountId, bool>,
         member_address_by_delegate_key: ink_storage::collections::HashMap<AccountId, AccountId>,
         proposals: Proposals,
+        /// the payload of proposals which are neither standard, whitelist nor guildkick proposals
+        proposal_kinds: ProposalKinds,
+        /// the parameters in force when each proposal was sponsored
+        proposal_parameters: ProposalParameters,
         proposal_queue: ProposalQueue,
+        /// the votes on each proposal by each member
+        votes_by_member: ink_storage::collections::HashMap<(ProposalId, AccountId), Vote>,
+        /// the ballots on each multi-option proposal
+        ballots: ink_storage::collections::HashMap<ProposalId, Vec<Ballot>>,
+        /// the member each member delegated its votes to
+        vote_delegates: ink_storage::collections::HashMap<AccountId, AccountId>,
+        /// the members who delegated their votes to each member
+        vote_delegators: ink_storage::collections::HashMap<AccountId, Vec<AccountId>>,
+        /// the delegate and the weight it counted for a delegator on each proposal
+        delegated_votes:
+            ink_storage::collections::HashMap<(ProposalId, AccountId), (AccountId, u128)>,
         /// total proposals submitted
         proposal_count: u128,
+        /// funding proposals passed by conviction voting, outside of the proposal queue
+        conviction_proposals: ConvictionProposals,
+        /// total conviction proposals submitted
+        conviction_proposal_count: u128,
+        /// the shares each member staked on each conviction proposal
+        conviction_stakes:
+            ink_storage::collections::HashMap<(ConvictionProposalId, AccountId), u128>,
+        /// the shares of each member staked on conviction proposals, which cannot be ragequit
+        staked_shares: ink_storage::collections::HashMap<AccountId, u128>,
+        /// the conviction proposals each member has shares staked on
+        staked_proposals: ink_storage::collections::HashMap<AccountId, Vec<ConvictionProposalId>>,
         /// total shares across all members
         total_shares: u128,
         /// total loot across all members
         total_loot: u128,
+        /// total weight of the shares of all members in a vote, used for the quorum
+        total_voting_weight: u128,
         /// total tokens with non-zero balance in guild bank
         total_guild_bank_tokens: u128,
         user_token_balances: ink_storage::collections::HashMap<(AccountId, AccountId), Balance>,
         summoning_time: Timestamp,
+        /// the companion token mirroring the shares of each member, if any
+        shares_token: Option<AccountId>,
+        /// the companion token mirroring the loot of each member, if any
+        loot_token: Option<AccountId>,
+        /// the deployment the state is exported to, set by a passed export proposal which
+        /// freezes the Moloch
+        successor: Option<AccountId>,
+        /// the tokens whose balances were moved to the successor
+        migrated_tokens: ink_storage::collections::HashMap<AccountId, bool>,
+        /// the deployment whose exported state this Moloch takes over, until it is imported
+        predecessor: Option<AccountId>,
+        /// the account allowed to veto sponsored proposals, if any
+        guardian: Option<AccountId>,
+        /// the period from which the guardian can no longer veto proposals
+        guardian_expiry_period: u128,
+        /// whether proposals and withdrawals are suspended, ragequit stays available
+        paused: bool,
+        /// the number of times the pause state was switched, votes only count in the current round
+        pause_round: u32,
+        /// whether each member voted to switch the pause state in each round
+        pause_votes: ink_storage::collections::HashMap<(u32, AccountId), bool>,
+        /// the members who voted to switch the pause state in each round
+        pause_voters: ink_storage::collections::HashMap<u32, Vec<AccountId>>,
+        /// the vesting schedule of the shares and loot requested by each proposal, if any
+        proposal_vesting: ink_storage::collections::HashMap<ProposalId, VestingSchedule>,
+        /// the shares and loot granted to each member and not fully claimed yet
+        vesting_grants: ink_storage::collections::HashMap<AccountId, Vec<VestingGrant>>,
+        /// total shares and loot granted but not claimed yet
+        total_unvested: u128,
     }
 
     impl Submoloch {
@@ -223,6 +579,8 @@ mod submoloch {
             proposal_deposit: u128,
             dilution_bound: u128,
             processing_reward: u128,
+            quorum_percentage: u128,
+            supermajority_percentage: Option<u128>,
         ) -> Self {
             assert!(summoner != AccountId::default(), "summoner cannot be 0");
             assert!(period_duration > 0, "_periodDuration cannot be 0");
@@ -240,6 +598,12 @@ mod submoloch {
                 dilution_bound <= constant::MAX_DILUTION_BOUND,
                 "_dilutionBound exceeds limit"
             );
+            assert!(quorum_percentage <= 100, "_quorumPercentage exceeds limit");
+            assert!(
+                supermajority_percentage
+                    .map_or(true, |percentage| percentage > 50 && percentage <= 100),
+                "_supermajorityPercentage must be greater than 50 and not exceed 100"
+            );
             assert!(
                 approved_tokens.len() as u128 > 0,
                 "need at least one approved token"
@@ -265,12 +629,15 @@ mod submoloch {
                 instance.approved_tokens.push(*i);
             }
 
+            instance.storage_version = STORAGE_VERSION;
             instance.period_duration = period_duration;
             instance.voting_period_length = voting_period_length;
             instance.grace_period_length = grace_period_length;
             instance.proposal_deposit = proposal_deposit;
             instance.dilution_bound = dilution_bound;
             instance.processing_reward = processing_reward;
+            instance.quorum_percentage = quorum_percentage;
+            instance.supermajority_percentage = supermajority_percentage;
             instance.summoning_time = instance.env().block_timestamp();
 
             let first_member = Member {
@@ -280,8 +647,11 @@ mod submoloch {
                 exists: true,
                 highest_index_yes_vote: 0,
                 jailed: 0,
+                unvested_shares: 0,
+                unvested_loot: 0,
             };
             instance.total_shares = first_member.shares;
+            instance.total_voting_weight = instance.voting_mode.weight(first_member.shares);
             instance.members.insert(summoner, first_member);
             instance
                 .member_address_by_delegate_key
@@ -301,10 +671,151 @@ mod submoloch {
                 proposal_deposit,
                 dilution_bound,
                 processing_reward,
+                quorum_percentage,
+                supermajority_percentage,
             });
             instance
         }
 
+        /// Summons a Moloch with the optional settings `new` leaves at their defaults.
+        ///
+        /// - `voting_mode` weighs the shares of each member in a vote.
+        /// - `guardian`, given as `(guardian, guardian_periods)`, may veto sponsored proposals
+        ///   during the first `guardian_periods` periods.
+        /// - `companion_tokens`, given as `(token_code_hash, transferable_loot)`, instantiates
+        ///   token contracts mirroring the shares and loot of each member from the erc20 code
+        ///   uploaded under `token_code_hash`, endowed with the value transferred to this
+        ///   constructor. Shares are never transferable, loot is transferable if
+        ///   `transferable_loot` is set.
+        #[ink(constructor)]
+        pub fn new_with_options(
+            summoner: AccountId,
+            approved_tokens: Vec<AccountId>,
+            period_duration: u16,
+            voting_period_length: u128,
+            grace_period_length: u128,
+            proposal_deposit: u128,
+            dilution_bound: u128,
+            processing_reward: u128,
+            quorum_percentage: u128,
+            supermajority_percentage: Option<u128>,
+            voting_mode: VotingMode,
+            guardian: Option<(AccountId, u128)>,
+            companion_tokens: Option<(Hash, bool)>,
+        ) -> Self {
+            if let Some((guardian, guardian_periods)) = guardian {
+                assert!(guardian != AccountId::default(), "_guardian cannot be 0");
+                assert!(guardian_periods > 0, "_guardianPeriods cannot be 0");
+            }
+            let mut instance = Self::new(
+                summoner,
+                approved_tokens,
+                period_duration,
+                voting_period_length,
+                grace_period_length,
+                proposal_deposit,
+                dilution_bound,
+                processing_reward,
+                quorum_percentage,
+                supermajority_percentage,
+            );
+
+            instance.voting_mode = voting_mode;
+            instance.total_voting_weight = voting_mode.weight(instance.total_shares);
+
+            if let Some((guardian, guardian_periods)) = guardian {
+                instance.guardian = Some(guardian);
+                instance.guardian_expiry_period = guardian_periods;
+            }
+
+            if let Some((token_code_hash, transferable_loot)) = companion_tokens {
+                let endowment = Self::env().transferred_balance() / 2;
+                let shares_token = Erc20::new_companion(
+                    Some(String::from("Submoloch Shares")),
+                    Some(String::from("SHARES")),
+                    false,
+                )
+                .endowment(endowment)
+                .code_hash(token_code_hash)
+                .salt_bytes(b"shares")
+                .instantiate()
+                .expect("failed at instantiating the shares token");
+                let loot_token = Erc20::new_companion(
+                    Some(String::from("Submoloch Loot")),
+                    Some(String::from("LOOT")),
+                    transferable_loot,
+                )
+                .endowment(endowment)
+                .code_hash(token_code_hash)
+                .salt_bytes(b"loot")
+                .instantiate()
+                .expect("failed at instantiating the loot token");
+                instance.shares_token = Some(shares_token.to_account_id());
+                instance.loot_token = Some(loot_token.to_account_id());
+
+                // the summoner's first share
+                instance._mint_companion_tokens(summoner, 1, 0);
+            }
+            instance
+        }
+
+        /// Rebuilds a Moloch from the state exported by another deployment.
+        ///
+        /// Companion tokens are not carried over. The exporting Moloch only moves its tokens
+        /// with `migrate_funds` to the successor named by its export proposal, so the rebuilt
+        /// balances are not backed by tokens until this contract is that successor and
+        /// `migrate_funds` ran for each token.
+        #[ink(constructor)]
+        pub fn from_snapshot(snapshot: StateSnapshot) -> Self {
+            assert!(
+                snapshot.version == STORAGE_VERSION,
+                "unsupported snapshot version"
+            );
+
+            let mut instance = Self::default();
+            instance.storage_version = STORAGE_VERSION;
+            instance._import_snapshot(snapshot);
+            instance
+        }
+
+        /// Deploys an empty Moloch taking over the state of `predecessor` once its members
+        /// approve an export proposal naming this contract, an alternative to
+        /// `from_snapshot` which lets the deployment be named before the export.
+        ///
+        /// The Moloch stays paused until `import_state` rebuilds the exported state.
+        #[ink(constructor)]
+        pub fn new_successor(predecessor: AccountId) -> Self {
+            let mut instance = Self::default();
+            instance.storage_version = STORAGE_VERSION;
+            instance.predecessor = Some(predecessor);
+            instance.paused = true;
+            instance
+        }
+
+        /// Defines a RPC call to get the companion token mirroring shares, if any.
+        #[ink(message)]
+        pub fn shares_token(&self) -> Option<AccountId> {
+            self.shares_token
+        }
+
+        /// Defines a RPC call to get the companion token mirroring loot, if any.
+        #[ink(message)]
+        pub fn loot_token(&self) -> Option<AccountId> {
+            self.loot_token
+        }
+
+        /// Defines a RPC call to get the pseudo token id of the native currency.
+        #[ink(message)]
+        pub fn native_token(&self) -> AccountId {
+            AccountId::from(NATIVE)
+        }
+
+        /// Defines a RPC call to get the version of the storage layout.
+        #[ink(message)]
+        pub fn storage_version(&self) -> u32 {
+            self.storage_version
+        }
+
         #[ink(message)]
         pub fn deposit_token(&self) -> AccountId {
             self.approved_tokens[0]
@@ -327,6 +838,11 @@ mod submoloch {
             self.approved_tokens[index]
         }
 
+        #[ink(message)]
+        pub fn delisted_tokens(&self, index: u32) -> Option<AccountId> {
+            self.delisted_tokens.get(index).copied()
+        }
+
         #[ink(message)]
         pub fn token_whitelist(&self, token_address: AccountId) -> bool {
             self.token_whitelist
@@ -335,6 +851,21 @@ mod submoloch {
                 .unwrap_or(false)
         }
 
+        /// Defines a RPC call to query the metadata of a whitelisted token.
+        #[ink(message)]
+        pub fn token_info(&self, token_address: AccountId) -> Option<TokenInfo> {
+            // the native currency has no metadata on chain
+            if !self.token_whitelist(token_address) || token_address == AccountId::from(NATIVE) {
+                return None;
+            }
+            let token: Erc20 = Erc20::from_account_id(token_address);
+            Some(TokenInfo {
+                name: token.token_name(),
+                symbol: token.token_symbol(),
+                decimals: token.token_decimals(),
+            })
+        }
+
         #[ink(message)]
         pub fn period_duration(&self) -> u16 {
             self.period_duration
@@ -386,8 +917,11 @@ mod submoloch {
         }
 
         #[ink(message)]
-        pub fn get_current_period(&self) -> u16 {
-            (self.env().block_timestamp() - self.summoning_time) as u16 / self.period_duration
+        pub fn get_current_period(&self) -> u128 {
+            // block timestamp is in milliseconds whereas period duration is in seconds.
+            ((self.env().block_timestamp() - self.summoning_time)
+                / 1000
+                / self.period_duration as u64) as u128
         }
 
         #[ink(message)]
@@ -395,8 +929,21 @@ mod submoloch {
             self.processing_reward
         }
 
-        /// Defines a RPC call to submit a proposal.
         #[ink(message)]
+        pub fn quorum_percentage(&self) -> u128 {
+            self.quorum_percentage
+        }
+
+        #[ink(message)]
+        pub fn supermajority_percentage(&self) -> Option<u128> {
+            self.supermajority_percentage
+        }
+
+        /// Defines a RPC call to submit a proposal.
+        ///
+        /// A tribute in the native currency is paid by transferring exactly
+        /// `tribute_offered` along with the call.
+        #[ink(message, payable)]
         pub fn submit_proposal(
             &mut self,
             applicant: AccountId,
@@ -408,6 +955,7 @@ mod submoloch {
             payment_token: AccountId,
             details: String,
         ) -> Result<ProposalId, String> {
+            self._ensure_not_paused()?;
             ensure!(
                 (shares_requested + loot_requested) <= constant::MAX_NUMBER_OF_SHARES_AND_LOOT,
                 "too many shares requested"
@@ -450,21 +998,39 @@ mod submoloch {
             let flags: [bool; 6] = Default::default(); // [sponsored, processed, didPass, cancelled, whitelist, guildkick]
 
             // collect tribute from proposer and store it in the Moloch until the proposal is processed
-            let mut token: Erc20 = Erc20::from_account_id(tribute_token);
-            let r: erc20::Result<()> = token.transfer_from(
-                self.env().caller(),
-                self.env().account_id(),
-                tribute_offered,
-            );
-            if r.is_err() {
-                match r.err().unwrap() {
-                    erc20::Error::InsufficientAllowance => {
-                        ink_env::debug_println("Erc20 Transfer fail - InsufficentAllowance");
-                        return Err(String::from("InsufficentAllowance"));
-                    }
-                    erc20::Error::InsufficientBalance => {
-                        ink_env::debug_println("Erc20 Transfer fail - InsufficentBalance.");
-                        return Err(String::from("InsufficentBalance"));
+            if tribute_token == AccountId::from(NATIVE) {
+                ensure!(
+                    self.env().transferred_balance() == tribute_offered,
+                    "transferred value must equal the tribute offered"
+                );
+            } else {
+                ensure!(
+                    self.env().transferred_balance() == 0,
+                    "tribute token is not the native currency"
+                );
+                let mut token: Erc20 = Erc20::from_account_id(tribute_token);
+                let r: erc20::Result<()> = token.transfer_from(
+                    self.env().caller(),
+                    self.env().account_id(),
+                    tribute_offered,
+                    Vec::new(),
+                );
+                if r.is_err() {
+                    match r.err().unwrap() {
+                        erc20::Error::InsufficientAllowance => {
+                            ink_env::debug_println("Erc20 Transfer fail - InsufficentAllowance");
+                            return Err(String::from("InsufficentAllowance"));
+                        }
+                        erc20::Error::InsufficientBalance => {
+                            ink_env::debug_println("Erc20 Transfer fail - InsufficentBalance.");
+                            return Err(String::from("InsufficentBalance"));
+                        }
+                        erc20::Error::Paused => {
+                            return Err(String::from("tribute token is paused"));
+                        }
+                        _ => {
+                            return Err(String::from("Erc20 Transfer fail"));
+                        }
                     }
                 }
             }
@@ -485,26 +1051,74 @@ mod submoloch {
             Ok(self.proposal_count - 1) // return proposalId - contracts calling submit might want it
         }
 
-        /// Defines a RPC call to submit a whitelist proposal.
-        #[ink(message)]
-        pub fn submit_whitelist_proposal(
+        /// Defines a RPC call to submit a proposal whose shares and loot vest along `schedule`.
+        ///
+        /// The tribute and payment are made when the proposal is processed as for any proposal.
+        #[ink(message, payable)]
+        pub fn submit_vesting_proposal(
             &mut self,
-            token_to_whitelist: AccountId,
+            applicant: AccountId,
+            shares_requested: u128,
+            loot_requested: u128,
+            tribute_offered: Balance,
+            tribute_token: AccountId,
+            payment_requested: Balance,
+            payment_token: AccountId,
             details: String,
+            schedule: VestingSchedule,
         ) -> Result<ProposalId, String> {
             ensure!(
-                token_to_whitelist != AccountId::default(),
-                "must provide token address"
+                schedule.cliff_periods <= constant::MAX_VESTING_PERIOD_LENGTH,
+                "cliff periods exceed limit"
             );
             ensure!(
-                !*self
-                    .token_whitelist
-                    .get(&token_to_whitelist)
-                    .unwrap_or(&false),
+                schedule.vesting_periods <= constant::MAX_VESTING_PERIOD_LENGTH,
+                "vesting periods exceed limit"
+            );
+            ensure!(
+                schedule.vesting_periods > 0 && schedule.cliff_periods <= schedule.vesting_periods,
+                "vesting periods must be positive and cover the cliff"
+            );
+            ensure!(
+                shares_requested > 0 || loot_requested > 0,
+                "must request shares or loot to vest"
+            );
+            let proposal_id = self.submit_proposal(
+                applicant,
+                shares_requested,
+                loot_requested,
+                tribute_offered,
+                tribute_token,
+                payment_requested,
+                payment_token,
+                details,
+            )?;
+            self.proposal_vesting.insert(proposal_id, schedule);
+            Ok(proposal_id)
+        }
+
+        /// Defines a RPC call to submit a whitelist proposal.
+        #[ink(message)]
+        pub fn submit_whitelist_proposal(
+            &mut self,
+            token_to_whitelist: AccountId,
+            details: String,
+        ) -> Result<ProposalId, String> {
+            self._ensure_not_paused()?;
+            ensure!(
+                token_to_whitelist != AccountId::default(),
+                "must provide token address"
+            );
+            ensure!(
+                !*self
+                    .token_whitelist
+                    .get(&token_to_whitelist)
+                    .unwrap_or(&false),
                 "cannot already have whitelisted the token"
             );
             ensure!(
-                (self.approved_tokens.len() as u128) < constant::MAX_TOKEN_WHITELIST_COUNT,
+                ((self.approved_tokens.len() + self.delisted_tokens.len()) as u128)
+                    < constant::MAX_TOKEN_WHITELIST_COUNT,
                 "cannot submit more whitelist proposals"
             );
 
@@ -524,6 +1138,190 @@ mod submoloch {
             Ok(self.proposal_count - 1)
         }
 
+        /// Defines a RPC call to submit a proposal to call another contract.
+        ///
+        /// `input` holds the SCALE-encoded arguments of the message with the given
+        /// `selector`, `value` is paid from the native currency in the guild bank.
+        #[ink(message)]
+        pub fn submit_action_proposal(
+            &mut self,
+            target: AccountId,
+            selector: [u8; 4],
+            input: Vec<u8>,
+            value: Balance,
+            details: String,
+        ) -> Result<ProposalId, String> {
+            self._ensure_not_paused()?;
+            ensure!(
+                target != AccountId::default(),
+                "must provide target address"
+            );
+            ensure!(
+                target != self.env().account_id(),
+                "action cannot target the Moloch itself"
+            );
+            ensure!(
+                !self._is_accounted_token(target),
+                "action cannot target a token accounted for by the Moloch"
+            );
+            ensure!(
+                value == 0 || self.token_whitelist(AccountId::from(NATIVE)),
+                "native currency is not whitelisted"
+            );
+
+            let flags: [bool; 6] = Default::default(); // [sponsored, processed, didPass, cancelled, whitelist, guildkick]
+            self._submit_proposal(None, 0, 0, None, None, None, None, details, flags);
+            let proposal_id = self.proposal_count - 1;
+            self.proposal_kinds.insert(
+                proposal_id,
+                ProposalKind::Action(Action {
+                    target,
+                    selector,
+                    input,
+                    value,
+                }),
+            );
+            Ok(proposal_id)
+        }
+
+        /// Defines a RPC call to submit a proposal to change the parameters of the DAO.
+        ///
+        /// Once passed, the new parameters apply to the proposals sponsored afterwards.
+        #[ink(message)]
+        pub fn submit_parameters_proposal(
+            &mut self,
+            voting_period_length: u128,
+            grace_period_length: u128,
+            proposal_deposit: u128,
+            dilution_bound: u128,
+            processing_reward: u128,
+            details: String,
+        ) -> Result<ProposalId, String> {
+            self._ensure_not_paused()?;
+            let parameters = Parameters {
+                voting_period_length,
+                grace_period_length,
+                proposal_deposit,
+                dilution_bound,
+                processing_reward,
+            };
+            Self::_validate_parameters(&parameters)?;
+
+            let flags: [bool; 6] = Default::default(); // [sponsored, processed, didPass, cancelled, whitelist, guildkick]
+            self._submit_proposal(None, 0, 0, None, None, None, None, details, flags);
+            let proposal_id = self.proposal_count - 1;
+            self.proposal_kinds
+                .insert(proposal_id, ProposalKind::Parameters(parameters));
+            Ok(proposal_id)
+        }
+
+        /// Defines a RPC call to submit a proposal removing a token from the whitelist.
+        ///
+        /// The guild bank balance of the token stays claimable through ragequit.
+        #[ink(message)]
+        pub fn submit_dewhitelist_proposal(
+            &mut self,
+            token_to_dewhitelist: AccountId,
+            details: String,
+        ) -> Result<ProposalId, String> {
+            self._ensure_not_paused()?;
+            ensure!(
+                self.token_whitelist(token_to_dewhitelist),
+                "token is not whitelisted"
+            );
+            ensure!(
+                token_to_dewhitelist != self.deposit_token(),
+                "cannot dewhitelist the deposit token"
+            );
+
+            let flags: [bool; 6] = Default::default(); // [sponsored, processed, didPass, cancelled, whitelist, guildkick]
+            self._submit_proposal(None, 0, 0, None, None, None, None, details, flags);
+            let proposal_id = self.proposal_count - 1;
+            self.proposal_kinds
+                .insert(proposal_id, ProposalKind::Dewhitelist(token_to_dewhitelist));
+            Ok(proposal_id)
+        }
+
+        /// Defines a RPC call to submit a proposal exporting the state to `successor`.
+        ///
+        /// `successor` is expected to be deployed with `new_successor`, naming this Moloch.
+        #[ink(message)]
+        pub fn submit_export_proposal(
+            &mut self,
+            successor: AccountId,
+            details: String,
+        ) -> Result<ProposalId, String> {
+            self._ensure_not_paused()?;
+            ensure!(
+                successor != AccountId::default() && successor != self.env().account_id(),
+                "invalid successor"
+            );
+            let flags: [bool; 6] = Default::default(); // [sponsored, processed, didPass, cancelled, whitelist, guildkick]
+            self._submit_proposal(None, 0, 0, None, None, None, None, details, flags);
+            let proposal_id = self.proposal_count - 1;
+            self.proposal_kinds
+                .insert(proposal_id, ProposalKind::ExportState(successor));
+            Ok(proposal_id)
+        }
+
+        /// Defines a RPC call to submit a proposal removing the guardian.
+        #[ink(message)]
+        pub fn submit_remove_guardian_proposal(
+            &mut self,
+            details: String,
+        ) -> Result<ProposalId, String> {
+            self._ensure_not_paused()?;
+            ensure!(self.guardian().is_some(), "there is no guardian to remove");
+            let flags: [bool; 6] = Default::default(); // [sponsored, processed, didPass, cancelled, whitelist, guildkick]
+            self._submit_proposal(None, 0, 0, None, None, None, None, details, flags);
+            let proposal_id = self.proposal_count - 1;
+            self.proposal_kinds
+                .insert(proposal_id, ProposalKind::RemoveGuardian);
+            Ok(proposal_id)
+        }
+
+        /// Defines a RPC call to submit a proposal choosing one of several options.
+        ///
+        /// The payment linked to the winning option, if any, is made when the proposal passes.
+        #[ink(message)]
+        pub fn submit_multi_option_proposal(
+            &mut self,
+            options: Vec<Option<Payment>>,
+            method: TallyMethod,
+            details: String,
+        ) -> Result<ProposalId, String> {
+            self._ensure_not_paused()?;
+            ensure!(
+                options.len() >= 2 && options.len() as u128 <= constant::MAX_PROPOSAL_OPTIONS,
+                "invalid number of options"
+            );
+            for payment in options.iter().flatten() {
+                ensure!(
+                    self.token_whitelist(payment.payment_token),
+                    "payment is not whitelisted"
+                );
+                ensure!(
+                    payment.applicant != AccountId::default(),
+                    "applicant cannot be 0"
+                );
+                ensure!(
+                    payment.applicant != AccountId::from(GUILD)
+                        && payment.applicant != AccountId::from(ESCROW)
+                        && payment.applicant != AccountId::from(TOTAL),
+                    "applicant address cannot be reserved"
+                );
+            }
+
+            let flags: [bool; 6] = Default::default(); // [sponsored, processed, didPass, cancelled, whitelist, guildkick]
+            self._submit_proposal(None, 0, 0, None, None, None, None, details, flags);
+            let proposal_id = self.proposal_count - 1;
+            self.proposal_kinds.insert(
+                proposal_id,
+                ProposalKind::MultiOption(MultiOption { options, method }),
+            );
+            Ok(proposal_id)
+        }
+
         /// Defines a RPC call to submit a guildkick proposal.
         #[ink(message)]
         pub fn submit_guildkick_proposal(
@@ -531,6 +1329,8 @@ mod submoloch {
             member_to_kick: AccountId,
             details: String,
         ) -> ProposalId {
+            assert!(self.successor.is_none(), "state has been exported");
+            assert!(!self.paused, "contract is paused");
             let member = self.members.get(&member_to_kick).unwrap();
 
             assert!(
@@ -605,16 +1405,38 @@ mod submoloch {
         }
 
         /// Defines a RPC call to sponsor a proposal.
-        #[ink(message)]
+        ///
+        /// If the deposit token is the native currency the sponsor transfers exactly
+        /// `proposal_deposit` along with the call.
+        #[ink(message, payable)]
         pub fn sponsor_proposal(&mut self, proposal_id: ProposalId) -> Result<(), String> {
+            self._ensure_not_paused()?;
             let caller = self.env().caller();
             // collect proposal deposit from sponsor and store it in the Moloch until the proposal is processed
             let deposit_token = self.deposit_token();
-            let mut token: Erc20 = Erc20::from_account_id(deposit_token);
-            ensure!(
-                token.transfer_from(caller, self.env().account_id(), self.proposal_deposit).is_ok(),
-                "proposal deposit token transfer failed"
-            );
+            if deposit_token == AccountId::from(NATIVE) {
+                ensure!(
+                    self.env().transferred_balance() == self.proposal_deposit,
+                    "transferred value must equal the proposal deposit"
+                );
+            } else {
+                ensure!(
+                    self.env().transferred_balance() == 0,
+                    "deposit token is not the native currency"
+                );
+                let mut token: Erc20 = Erc20::from_account_id(deposit_token);
+                ensure!(
+                    token
+                        .transfer_from(
+                            caller,
+                            self.env().account_id(),
+                            self.proposal_deposit,
+                            Vec::new()
+                        )
+                        .is_ok(),
+                    "proposal deposit token transfer failed"
+                );
+            }
             self.unsafe_add_to_balance(AccountId::from(ESCROW), deposit_token, self.proposal_deposit);
 
             // compute startingPeriod for proposal
@@ -628,33 +1450,35 @@ mod submoloch {
                             .copied()
                             .map_or(0, |proposal| proposal.starting_period)
                     });
-            let current_period = self.get_current_period() as u128;
+            let current_period = self.get_current_period();
             let starting_period = utils::max(current_period, last_starting_period) + 1;
 
+            let parameters = self._current_parameters();
+
             let maybe_proposal: Option<&mut Proposal> = self.proposals.get_mut(&proposal_id);
             match maybe_proposal {
                 Some(proposal) => {
-                    let tribute_token = proposal
-                        .tribute_token
-                        .expect("proposal has no tribute token.");
-
-                    let applicant = proposal.applicant.expect("proposal has no applicant");
-
-                    let member = self
-                        .members
-                        .get(&applicant)
-                        .expect("proposal has no member");
-
                     ensure!(
                         proposal.proposer != AccountId::default(),
                         "proposal must have been proposed"
                     );
                     ensure!(!proposal.flags[0], "proposal has already been sponsored");
                     ensure!(!proposal.flags[3], "proposal has been cancelled");
-                    ensure!(member.jailed == 0, "proposal applicant must not be jailed");
+                    // whitelist proposals have no applicant, applicants of other proposals need not be members yet.
+                    let applicant_jailed = match proposal.applicant {
+                        Some(applicant) => self
+                            .members
+                            .get(&applicant)
+                            .map_or(false, |member| member.jailed != 0),
+                        None => false,
+                    };
+                    ensure!(!applicant_jailed, "proposal applicant must not be jailed");
 
                     // whitelist proposal
                     if proposal.flags[4] {
+                        let tribute_token = proposal
+                            .tribute_token
+                            .expect("proposal has no tribute token.");
                         ensure!(
                             !*self.token_whitelist.get(&tribute_token).unwrap_or(&false),
                             "cannot already have whitelisted the token"
@@ -667,7 +1491,7 @@ mod submoloch {
                             "already proposed to whitelist"
                         );
                         ensure!(
-                            (self.approved_tokens.len() as u128)
+                            ((self.approved_tokens.len() + self.delisted_tokens.len()) as u128)
                                 < constant::MAX_TOKEN_WHITELIST_COUNT,
                             "cannot sponsor more whitelist proposals"
                         );
@@ -675,14 +1499,30 @@ mod submoloch {
 
                         // guild kick proposal
                     } else if proposal.flags[5] {
+                        let applicant = proposal.applicant.expect("proposal has no applicant");
                         ensure!(
                             !*self.proposed_to_kick.get(&applicant).unwrap_or(&false),
                             "already proposed to kick"
                         );
                         self.proposed_to_kick.insert(applicant, true);
+
+                        // dewhitelist proposal
+                    } else if let Some(ProposalKind::Dewhitelist(token)) =
+                        self.proposal_kinds.get(&proposal_id)
+                    {
+                        ensure!(
+                            *self.token_whitelist.get(token).unwrap_or(&false),
+                            "token is not whitelisted"
+                        );
+                        ensure!(
+                            !*self.proposed_to_dewhitelist.get(token).unwrap_or(&false),
+                            "already proposed to dewhitelist"
+                        );
+                        self.proposed_to_dewhitelist.insert(*token, true);
                     }
 
                     proposal.starting_period = starting_period;
+                    self.proposal_parameters.insert(proposal_id, parameters);
 
                     let member_address: AccountId = *self
                         .member_address_by_delegate_key
@@ -709,97 +1549,2016 @@ mod submoloch {
             }
         }
 
-        /// Defines a RPC call to checking voting period.
+        /// Defines a RPC call to checking voting period, under the current parameters.
         #[ink(message)]
-        pub fn has_voting_period_expired(&self, _starting_period: u128) -> bool {
-            unimplemented!()
+        pub fn has_voting_period_expired(&self, starting_period: u128) -> bool {
+            self.get_current_period() >= starting_period + self.voting_period_length
         }
 
-        /// Defines a RPC call to submit a vote.
+        /// Defines a RPC call to checking the voting period of a proposal, under the parameters
+        /// the proposal was sponsored with.
         #[ink(message)]
-        pub fn submit_vote(&self, _proposal_index: ProposalIndex, _uintvote: u8) -> bool {
-            unimplemented!()
+        pub fn has_proposal_voting_period_expired(&self, proposal_id: ProposalId) -> bool {
+            self.proposals(proposal_id).map_or(false, |proposal| {
+                self.get_current_period()
+                    >= proposal.starting_period
+                        + self._proposal_parameters(proposal_id).voting_period_length
+            })
         }
 
-        /// Defines a RPC call to process proposal.
+        /// Defines a RPC call to submit a vote.
         #[ink(message)]
-        pub fn process_proposal(&self, _proposal_index: ProposalIndex) -> bool {
-            unimplemented!()
-        }
+        pub fn submit_vote(
+            &mut self,
+            proposal_index: ProposalIndex,
+            uint_vote: u8,
+        ) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let caller = self.env().caller();
+            let member_address = self.member_address_by_delegate_key(caller);
+            ensure!(
+                member_address
+                    .and_then(|member_address| self.members(member_address))
+                    .map_or(false, |member| member.shares > 0),
+                "not a delegate"
+            );
+            let member_address = member_address.unwrap();
+            ensure!(
+                proposal_index < self.proposal_queue.len() as u128,
+                "proposal does not exist"
+            );
+            let proposal_id = self.proposal_queue[proposal_index as u32];
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            let mut member = self.members(member_address).unwrap();
+            ensure!(uint_vote < 3, "must be less than 3");
+            let vote = Vote::from_u8(uint_vote).unwrap();
+            ensure!(
+                self.get_current_period() >= proposal.starting_period,
+                "voting period has not started"
+            );
+            ensure!(
+                !self.has_proposal_voting_period_expired(proposal_id),
+                "voting period has expired"
+            );
+            ensure!(
+                self.get_member_proposal_vote(member_address, proposal_index) == Vote::None,
+                "member has already voted"
+            );
+            ensure!(
+                vote == Vote::Yes || vote == Vote::No,
+                "vote must be either Yes or No"
+            );
+            ensure!(
+                !matches!(
+                    self.proposal_kinds.get(&proposal_id),
+                    Some(ProposalKind::MultiOption(_))
+                ),
+                "must rank the options of a multi-option proposal"
+            );
 
-        /// Defines a RPC call to process whitelist proposal.
-        #[ink(message)]
-        pub fn process_whitelist_proposal(&self, _proposal_index: ProposalIndex) -> bool {
-            unimplemented!()
-        }
+            self.votes_by_member
+                .insert((proposal_id, member_address), vote);
 
-        /// Defines a RPC call to process guildkick proposal.
-        #[ink(message)]
-        pub fn process_guildkick_proposal(&self, _proposal_index: ProposalIndex) -> bool {
-            unimplemented!()
-        }
+            // a vote cast directly overrides the one cast on the member's behalf by its delegate
+            if let Some((delegate, delegated_weight)) =
+                self.delegated_votes.take(&(proposal_id, member_address))
+            {
+                match self.votes_by_member.get(&(proposal_id, delegate)) {
+                    Some(Vote::Yes) => proposal.yes_votes -= delegated_weight,
+                    Some(Vote::No) => proposal.no_votes -= delegated_weight,
+                    _ => {}
+                }
+            }
 
-        /// Defines a RPC call to check if the member can ragequit.
-        #[ink(message)]
-        pub fn can_ragequit(&self) -> bool {
-            unimplemented!()
-        }
+            // count the shares of the delegators who have not voted themselves, nor been counted
+            // by a previous delegate
+            let mut weight = self.voting_mode.weight(member.shares);
+            let delegators = self
+                .vote_delegators
+                .get(&member_address)
+                .cloned()
+                .unwrap_or_default();
+            for delegator_address in delegators {
+                if self
+                    .votes_by_member
+                    .contains_key(&(proposal_id, delegator_address))
+                    || self
+                        .delegated_votes
+                        .contains_key(&(proposal_id, delegator_address))
+                {
+                    continue;
+                }
+                let mut delegator = match self.members(delegator_address) {
+                    Some(delegator) if delegator.shares > 0 => delegator,
+                    _ => continue,
+                };
+                let delegated_weight = self.voting_mode.weight(delegator.shares);
+                weight += delegated_weight;
+                self.delegated_votes.insert(
+                    (proposal_id, delegator_address),
+                    (member_address, delegated_weight),
+                );
 
-        /// Defines a RPC call to ragekick.
-        #[ink(message)]
-        pub fn ragequit(&self, _shares_to_burn: u128, _loot_to_burn: u128) -> bool {
-            unimplemented!()
-        }
+                // a delegator counted as a YES voter cannot ragequit before the proposal is processed either
+                if vote == Vote::Yes && proposal_index > delegator.highest_index_yes_vote {
+                    delegator.highest_index_yes_vote = proposal_index;
+                    self.members.insert(delegator_address, delegator);
+                }
+            }
 
-        /// Defines a RPC call to ragekick.
-        #[ink(message)]
-        pub fn ragekick(&self, _member_to_kick: AccountId) -> bool {
-            unimplemented!()
-        }
+            if vote == Vote::Yes {
+                proposal.yes_votes += weight;
 
-        /// Defines a RPC call to withdraw a single token balance.
-        #[ink(message)]
-        pub fn withdraw_balance(&self, _token: AccountId, _amount: u128) -> bool {
-            unimplemented!()
-        }
+                // set highest index (latest) yes vote - must be processed for member to ragequit
+                if proposal_index > member.highest_index_yes_vote {
+                    member.highest_index_yes_vote = proposal_index;
+                    self.members.insert(member_address, member);
+                }
 
-        /// Defines a RPC call to withdraw multiple token balances at once.
-        #[ink(message)]
-        pub fn withdraw_balances(&self, _tokens: Vec<AccountId>, _amounts: Vec<u128>) -> bool {
-            unimplemented!()
-        }
+                // set maximum of total shares encountered at a yes vote - used to bound dilution for yes voters
+                proposal.max_total_shares_and_loot_at_yes_vote = utils::max(
+                    proposal.max_total_shares_and_loot_at_yes_vote,
+                    self.total_shares + self.total_loot,
+                );
+            } else if vote == Vote::No {
+                proposal.no_votes += weight;
+            }
+            self.proposals.insert(proposal_id, proposal);
 
-        #[ink(message)]
-        pub fn collect_tokens(&self, _token: AccountId) -> bool {
-            unimplemented!()
+            self.env().emit_event(SubmitVote {
+                proposal_id,
+                proposal_index,
+                delegate_key: caller,
+                member_address,
+                uint_vote: uint_vote as u128,
+            });
+            Ok(())
         }
 
+        /// Defines a RPC call to rank the options of a multi-option proposal, most preferred first.
+        ///
+        /// A ballot counts as a YES vote towards the quorum and the dilution bound. Voting power
+        /// delegated by other members only counts on YES/NO votes.
         #[ink(message)]
-        pub fn cancel_proposal(&self, _proposal_id: ProposalId) -> bool {
-            unimplemented!()
-        }
+        pub fn submit_ranked_vote(
+            &mut self,
+            proposal_index: ProposalIndex,
+            ranking: Vec<u8>,
+        ) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let caller = self.env().caller();
+            let member_address = self.member_address_by_delegate_key(caller);
+            ensure!(
+                member_address
+                    .and_then(|member_address| self.members(member_address))
+                    .map_or(false, |member| member.shares > 0),
+                "not a delegate"
+            );
+            let member_address = member_address.unwrap();
+            ensure!(
+                proposal_index < self.proposal_queue.len() as u128,
+                "proposal does not exist"
+            );
+            let proposal_id = self.proposal_queue[proposal_index as u32];
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            let mut member = self.members(member_address).unwrap();
+            let option_count = match self.proposal_kinds.get(&proposal_id) {
+                Some(ProposalKind::MultiOption(multi_option)) => multi_option.options.len(),
+                _ => return Err(String::from("must be a multi-option proposal")),
+            };
+            ensure!(
+                self.get_current_period() >= proposal.starting_period,
+                "voting period has not started"
+            );
+            ensure!(
+                !self.has_proposal_voting_period_expired(proposal_id),
+                "voting period has expired"
+            );
+            let mut ballots = self.ballots(proposal_id);
+            ensure!(
+                !ballots
+                    .iter()
+                    .any(|ballot| ballot.member_address == member_address),
+                "member has already voted"
+            );
+            ensure!(
+                multi_option::is_valid_ranking(&ranking, option_count),
+                "ranking must hold distinct options"
+            );
 
-        #[ink(message)]
-        pub fn update_delegate_key(&self, _new_delegate_key: AccountId) -> bool {
-            unimplemented!()
-        }
+            let weight = self.voting_mode.weight(member.shares);
+            ballots.push(Ballot {
+                member_address,
+                weight,
+                ranking: ranking.clone(),
+            });
+            self.ballots.insert(proposal_id, ballots);
 
-        #[ink(message)]
-        pub fn get_proposal_queue_length(&self) -> u128 {
-            self.proposal_queue.len() as u128
+            proposal.yes_votes += weight;
+
+            // set highest index (latest) yes vote - must be processed for member to ragequit
+            if proposal_index > member.highest_index_yes_vote {
+                member.highest_index_yes_vote = proposal_index;
+                self.members.insert(member_address, member);
+            }
+
+            // set maximum of total shares encountered at a yes vote - used to bound dilution for yes voters
+            proposal.max_total_shares_and_loot_at_yes_vote = utils::max(
+                proposal.max_total_shares_and_loot_at_yes_vote,
+                self.total_shares + self.total_loot,
+            );
+            self.proposals.insert(proposal_id, proposal);
+
+            self.env().emit_event(SubmitRankedVote {
+                proposal_id,
+                proposal_index,
+                delegate_key: caller,
+                member_address,
+                ranking,
+            });
+            Ok(())
         }
 
+        /// Defines a RPC call to delegate the voting power of the caller's shares to another member.
+        ///
+        /// Delegation is one level deep: a member who has delegators cannot delegate, and a
+        /// member who delegated cannot be delegated to.
         #[ink(message)]
-        pub fn get_proposal_flags(&self, proposal_id: ProposalId) -> Option<[bool; 6]> {
-            match self.proposals(proposal_id) {
-                Some(proposal) => Some(proposal.flags),
-                None => None,
-            }
+        pub fn delegate_votes(&mut self, delegate: AccountId) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let caller = self.env().caller();
+            ensure!(
+                self.members(caller)
+                    .map_or(false, |member| member.shares > 0),
+                "member must have at least one share"
+            );
+            ensure!(delegate != caller, "cannot delegate to self");
+            ensure!(
+                self.members(delegate)
+                    .map_or(false, |member| member.exists && member.jailed == 0),
+                "delegate must be a member"
+            );
+            ensure!(
+                !self.vote_delegates.contains_key(&delegate),
+                "delegate has delegated its votes"
+            );
+            ensure!(
+                self.vote_delegators
+                    .get(&caller)
+                    .map_or(true, |delegators| delegators.is_empty()),
+                "member has delegators"
+            );
+            let mut delegators = self
+                .vote_delegators
+                .get(&delegate)
+                .cloned()
+                .unwrap_or_default();
+            ensure!(
+                (delegators.len() as u128) < constant::MAX_VOTE_DELEGATORS,
+                "delegate has too many delegators"
+            );
+
+            let from_delegate = self._revoke_vote_delegate(caller);
+            delegators.push(caller);
+            self.vote_delegators.insert(delegate, delegators);
+            self.vote_delegates.insert(caller, delegate);
+
+            self.env().emit_event(DelegateVotes {
+                delegator: caller,
+                from_delegate,
+                to_delegate: Some(delegate),
+            });
+            Ok(())
         }
 
+        /// Defines a RPC call to take back the voting power delegated by the caller.
+        ///
+        /// Votes already cast by the delegate stand until the caller votes directly.
         #[ink(message)]
-        pub fn user_token_balances(&self, user: AccountId, token: AccountId) -> Balance {
-            *self.user_token_balances.get(&(user, token)).unwrap_or(&0)
+        pub fn undelegate_votes(&mut self) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let caller = self.env().caller();
+            let from_delegate = self._revoke_vote_delegate(caller);
+            ensure!(from_delegate.is_some(), "votes are not delegated");
+
+            self.env().emit_event(DelegateVotes {
+                delegator: caller,
+                from_delegate,
+                to_delegate: None,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to process proposal.
+        #[ink(message)]
+        pub fn process_proposal(&mut self, proposal_index: ProposalIndex) -> Result<(), String> {
+            let proposal_id = self._validate_proposal_for_processing(proposal_index)?;
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            ensure!(
+                !proposal.flags[4]
+                    && !proposal.flags[5]
+                    && !self.proposal_kinds.contains_key(&proposal_id),
+                "must be a standard proposal"
+            );
+
+            proposal.flags[1] = true; // processed
+
+            let applicant = proposal.applicant.expect("proposal has no applicant");
+            let tribute_offered = proposal.tribute_offered.unwrap_or(0);
+            let tribute_token = proposal
+                .tribute_token
+                .expect("proposal has no tribute token");
+            let payment_requested = proposal.payment_requested.unwrap_or(0);
+            let payment_token = proposal
+                .payment_token
+                .expect("proposal has no payment token");
+
+            let vesting_schedule = self.proposal_vesting.get(&proposal_id).copied();
+
+            let mut did_pass = self._did_pass(proposal_id, &proposal);
+
+            // Make the proposal fail if the new total number of shares and loot exceeds the limit
+            if self.total_shares
+                + self.total_loot
+                + self.total_unvested
+                + proposal.shares_requested
+                + proposal.loot_requested
+                > constant::MAX_NUMBER_OF_SHARES_AND_LOOT
+            {
+                did_pass = false;
+            }
+
+            // Make the proposal fail if its tokens have been removed from the whitelist since it was submitted
+            if (tribute_offered > 0 && !self.token_whitelist(tribute_token))
+                || (payment_requested > 0 && !self.token_whitelist(payment_token))
+            {
+                did_pass = false;
+            }
+
+            // Make the proposal fail if it is requesting more tokens as payment than the available guild bank balance
+            if payment_requested > self.user_token_balances(AccountId::from(GUILD), payment_token) {
+                did_pass = false;
+            }
+
+            // Make the proposal fail if it would result in too many tokens with non-zero balance in guild bank
+            if tribute_offered > 0
+                && self.user_token_balances(AccountId::from(GUILD), tribute_token) == 0
+                && self.total_guild_bank_tokens >= constant::MAX_TOKEN_GUILDBANK_COUNT
+            {
+                did_pass = false;
+            }
+
+            // PROPOSAL PASSED
+            if did_pass {
+                proposal.flags[2] = true; // didPass
+
+                // shares & loot granted with a vesting schedule are only minted once claimed
+                let (shares_granted, loot_granted) = match vesting_schedule {
+                    Some(_) => (0, 0),
+                    None => (proposal.shares_requested, proposal.loot_requested),
+                };
+
+                // if the applicant is already a member, add to their existing shares & loot
+                if self
+                    .members(applicant)
+                    .map_or(false, |member| member.exists)
+                {
+                    let member = self.members.get_mut(&applicant).unwrap();
+                    let old_shares = member.shares;
+                    member.shares += shares_granted;
+                    member.loot += loot_granted;
+                    let new_shares = member.shares;
+                    self._update_total_voting_weight(old_shares, new_shares);
+
+                // the applicant is a new member, create a new record for them
+                } else {
+                    // if the applicant address is already taken by a member's delegateKey, reset it to their member address
+                    if let Some(member_to_override) = self.member_address_by_delegate_key(applicant)
+                    {
+                        if let Some(member) = self.members.get_mut(&member_to_override) {
+                            member.delegate_key = member_to_override;
+                            self.member_address_by_delegate_key
+                                .insert(member_to_override, member_to_override);
+                        }
+                    }
+
+                    // use applicant address as delegateKey by default
+                    let mut member = Member::new(applicant);
+                    member.shares = shares_granted;
+                    member.loot = loot_granted;
+                    self.members.insert(applicant, member);
+                    self._update_total_voting_weight(0, shares_granted);
+                    self.member_address_by_delegate_key
+                        .insert(applicant, applicant);
+                }
+
+                // mint new shares & loot
+                self.total_shares += shares_granted;
+                self.total_loot += loot_granted;
+                self._mint_companion_tokens(applicant, shares_granted, loot_granted);
+
+                // hold the shares & loot to vest until claimed
+                if let Some(schedule) = vesting_schedule {
+                    let member = self.members.get_mut(&applicant).unwrap();
+                    member.unvested_shares += proposal.shares_requested;
+                    member.unvested_loot += proposal.loot_requested;
+                    self.total_unvested += proposal.shares_requested + proposal.loot_requested;
+
+                    let mut grants = self
+                        .vesting_grants
+                        .get(&applicant)
+                        .cloned()
+                        .unwrap_or_default();
+                    grants.push(VestingGrant {
+                        shares: proposal.shares_requested,
+                        loot: proposal.loot_requested,
+                        start_period: self.get_current_period(),
+                        schedule,
+                        claimed_shares: 0,
+                        claimed_loot: 0,
+                    });
+                    self.vesting_grants.insert(applicant, grants);
+                }
+
+                // if the proposal tribute is the first tokens of its kind to make it into the guild bank, increment total guild bank tokens
+                if self.user_token_balances(AccountId::from(GUILD), tribute_token) == 0
+                    && tribute_offered > 0
+                {
+                    self.total_guild_bank_tokens += 1;
+                }
+
+                self.unsafe_internal_transfer(
+                    AccountId::from(ESCROW),
+                    AccountId::from(GUILD),
+                    tribute_token,
+                    tribute_offered,
+                );
+                self.unsafe_internal_transfer(
+                    AccountId::from(GUILD),
+                    applicant,
+                    payment_token,
+                    payment_requested,
+                );
+
+                // if the proposal spends 100% of guild bank balance for a token, decrement total guild bank tokens
+                if self.user_token_balances(AccountId::from(GUILD), payment_token) == 0
+                    && payment_requested > 0
+                {
+                    self.total_guild_bank_tokens -= 1;
+                }
+
+            // PROPOSAL FAILED
+            } else {
+                // return all tokens to the proposer (not the applicant, because funds come from proposer)
+                self.unsafe_internal_transfer(
+                    AccountId::from(ESCROW),
+                    proposal.proposer,
+                    tribute_token,
+                    tribute_offered,
+                );
+            }
+
+            self.proposals.insert(proposal_id, proposal);
+            self._settle_deposit(
+                proposal_id,
+                proposal.sponsor.expect("proposal has no sponsor"),
+            );
+
+            self.env().emit_event(ProcessProposal {
+                proposal_index,
+                proposal_id,
+                did_pass,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to process whitelist proposal.
+        #[ink(message)]
+        pub fn process_whitelist_proposal(
+            &mut self,
+            proposal_index: ProposalIndex,
+        ) -> Result<(), String> {
+            let proposal_id = self._validate_proposal_for_processing(proposal_index)?;
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            ensure!(proposal.flags[4], "must be a whitelist proposal");
+
+            proposal.flags[1] = true; // processed
+
+            let token_to_whitelist = proposal
+                .tribute_token
+                .expect("proposal has no tribute token");
+            let mut did_pass = self._did_pass(proposal_id, &proposal);
+
+            if (self.approved_tokens.len() + self.delisted_tokens.len()) as u128
+                >= constant::MAX_TOKEN_WHITELIST_COUNT
+            {
+                did_pass = false;
+            }
+
+            if did_pass {
+                proposal.flags[2] = true; // didPass
+
+                self.token_whitelist.insert(token_to_whitelist, true);
+                self.approved_tokens.push(token_to_whitelist);
+
+                // a token whitelisted again is no longer tracked as delisted
+                if let Some(index) = self
+                    .delisted_tokens
+                    .iter()
+                    .position(|delisted| *delisted == token_to_whitelist)
+                {
+                    self.delisted_tokens.swap_remove_drop(index as u32);
+                }
+            }
+
+            self.proposed_to_whitelist.insert(token_to_whitelist, false);
+
+            self.proposals.insert(proposal_id, proposal);
+            self._settle_deposit(
+                proposal_id,
+                proposal.sponsor.expect("proposal has no sponsor"),
+            );
+
+            self.env().emit_event(ProcessWhitelistProposal {
+                proposal_index,
+                proposal_id,
+                did_pass,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to process guildkick proposal.
+        #[ink(message)]
+        pub fn process_guildkick_proposal(
+            &mut self,
+            proposal_index: ProposalIndex,
+        ) -> Result<(), String> {
+            let proposal_id = self._validate_proposal_for_processing(proposal_index)?;
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            ensure!(proposal.flags[5], "must be a guild kick proposal");
+
+            proposal.flags[1] = true; // processed
+
+            let member_to_kick = proposal.applicant.expect("proposal has no applicant");
+            let did_pass = self._did_pass(proposal_id, &proposal);
+
+            if did_pass {
+                proposal.flags[2] = true; // didPass
+
+                let member = self
+                    .members
+                    .get_mut(&member_to_kick)
+                    .expect("member not found");
+                member.jailed = proposal_index;
+
+                // transfer shares to loot
+                let kicked_shares = member.shares;
+                member.loot += kicked_shares;
+                member.shares = 0; // revoke all shares
+                self.total_shares -= kicked_shares;
+                self.total_loot += kicked_shares;
+                self._update_total_voting_weight(kicked_shares, 0);
+                self._burn_companion_tokens(member_to_kick, kicked_shares, 0);
+                self._mint_companion_tokens(member_to_kick, 0, kicked_shares);
+                // the shares turned into loot no longer back conviction
+                self._unstake_all_conviction(member_to_kick);
+            }
+
+            self.proposed_to_kick.insert(member_to_kick, false);
+
+            self.proposals.insert(proposal_id, proposal);
+            self._settle_deposit(
+                proposal_id,
+                proposal.sponsor.expect("proposal has no sponsor"),
+            );
+
+            self.env().emit_event(ProcessGuildKickProposal {
+                proposal_index,
+                proposal_id,
+                did_pass,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to process an action proposal.
+        ///
+        /// The call is made once the proposal passed, its outcome is recorded in an
+        /// `ExecuteAction` event. The value of a failed call is returned to the guild bank.
+        #[ink(message)]
+        pub fn process_action_proposal(
+            &mut self,
+            proposal_index: ProposalIndex,
+        ) -> Result<(), String> {
+            let proposal_id = self._validate_proposal_for_processing(proposal_index)?;
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            let action = match self.proposal_kinds.get(&proposal_id) {
+                Some(ProposalKind::Action(action)) => action.clone(),
+                _ => return Err(String::from("must be an action proposal")),
+            };
+
+            proposal.flags[1] = true; // processed
+
+            let mut did_pass = self._did_pass(proposal_id, &proposal);
+
+            // Make the proposal fail if its target became a token accounted for by the Moloch since it was submitted
+            if self._is_accounted_token(action.target) {
+                did_pass = false;
+            }
+
+            // Make the proposal fail if the guild bank cannot pay the value of the call
+            if action.value
+                > self.user_token_balances(AccountId::from(GUILD), AccountId::from(NATIVE))
+            {
+                did_pass = false;
+            }
+
+            if did_pass {
+                proposal.flags[2] = true; // didPass
+            }
+
+            self.proposals.insert(proposal_id, proposal);
+            self._settle_deposit(
+                proposal_id,
+                proposal.sponsor.expect("proposal has no sponsor"),
+            );
+
+            if did_pass {
+                let guild = AccountId::from(GUILD);
+                let native = AccountId::from(NATIVE);
+                self.unsafe_subtract_from_balance(guild, native, action.value);
+                let result = build_call::<Environment>()
+                    .callee(action.target)
+                    .gas_limit(0)
+                    .transferred_value(action.value)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(action.selector))
+                            .push_arg(CallInput(&action.input)),
+                    )
+                    .returns::<ReturnType<CallOutput>>()
+                    .fire();
+                let success = result.is_ok();
+                let output = result.map_or_else(|_| Vec::new(), |CallOutput(output)| output);
+                if !success {
+                    self.unsafe_add_to_balance(guild, native, action.value);
+                } else if action.value > 0 && self.user_token_balances(guild, native) == 0 {
+                    self.total_guild_bank_tokens -= 1;
+                }
+                self.env().emit_event(ExecuteAction {
+                    proposal_id,
+                    target: action.target,
+                    value: action.value,
+                    success,
+                    output,
+                });
+            }
+
+            self.env().emit_event(ProcessActionProposal {
+                proposal_index,
+                proposal_id,
+                did_pass,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to process a parameters proposal.
+        #[ink(message)]
+        pub fn process_parameters_proposal(
+            &mut self,
+            proposal_index: ProposalIndex,
+        ) -> Result<(), String> {
+            let proposal_id = self._validate_proposal_for_processing(proposal_index)?;
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            let parameters = match self.proposal_kinds.get(&proposal_id) {
+                Some(ProposalKind::Parameters(parameters)) => *parameters,
+                _ => return Err(String::from("must be a parameters proposal")),
+            };
+
+            proposal.flags[1] = true; // processed
+
+            let did_pass = self._did_pass(proposal_id, &proposal);
+
+            if did_pass {
+                proposal.flags[2] = true; // didPass
+            }
+
+            self.proposals.insert(proposal_id, proposal);
+            self._settle_deposit(
+                proposal_id,
+                proposal.sponsor.expect("proposal has no sponsor"),
+            );
+
+            if did_pass {
+                self.voting_period_length = parameters.voting_period_length;
+                self.grace_period_length = parameters.grace_period_length;
+                self.proposal_deposit = parameters.proposal_deposit;
+                self.dilution_bound = parameters.dilution_bound;
+                self.processing_reward = parameters.processing_reward;
+                self.env().emit_event(ParametersUpdated {
+                    proposal_id,
+                    voting_period_length: parameters.voting_period_length,
+                    grace_period_length: parameters.grace_period_length,
+                    proposal_deposit: parameters.proposal_deposit,
+                    dilution_bound: parameters.dilution_bound,
+                    processing_reward: parameters.processing_reward,
+                });
+            }
+
+            self.env().emit_event(ProcessParametersProposal {
+                proposal_index,
+                proposal_id,
+                did_pass,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to process a dewhitelist proposal.
+        ///
+        /// The token is swapped out of `approved_tokens`, which never moves the deposit token
+        /// at index 0, and kept in `delisted_tokens` while the guild bank holds some of it.
+        #[ink(message)]
+        pub fn process_dewhitelist_proposal(
+            &mut self,
+            proposal_index: ProposalIndex,
+        ) -> Result<(), String> {
+            let proposal_id = self._validate_proposal_for_processing(proposal_index)?;
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            let token_to_dewhitelist = match self.proposal_kinds.get(&proposal_id) {
+                Some(ProposalKind::Dewhitelist(token)) => *token,
+                _ => return Err(String::from("must be a dewhitelist proposal")),
+            };
+
+            proposal.flags[1] = true; // processed
+
+            let did_pass = self._did_pass(proposal_id, &proposal);
+
+            if did_pass {
+                proposal.flags[2] = true; // didPass
+
+                self.token_whitelist.insert(token_to_dewhitelist, false);
+                let index = self
+                    .approved_tokens
+                    .iter()
+                    .position(|approved| *approved == token_to_dewhitelist)
+                    .expect("token is not approved");
+                self.approved_tokens.swap_remove_drop(index as u32);
+                if self.user_token_balances(AccountId::from(GUILD), token_to_dewhitelist) > 0 {
+                    self.delisted_tokens.push(token_to_dewhitelist);
+                }
+            }
+
+            self.proposed_to_dewhitelist
+                .insert(token_to_dewhitelist, false);
+
+            self.proposals.insert(proposal_id, proposal);
+            self._settle_deposit(
+                proposal_id,
+                proposal.sponsor.expect("proposal has no sponsor"),
+            );
+
+            self.env().emit_event(ProcessDewhitelistProposal {
+                proposal_index,
+                proposal_id,
+                did_pass,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to process an export proposal.
+        #[ink(message)]
+        pub fn process_export_proposal(
+            &mut self,
+            proposal_index: ProposalIndex,
+        ) -> Result<(), String> {
+            let proposal_id = self._validate_proposal_for_processing(proposal_index)?;
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            let successor = match self.proposal_kinds.get(&proposal_id) {
+                Some(ProposalKind::ExportState(successor)) => *successor,
+                _ => return Err(String::from("must be an export proposal")),
+            };
+
+            proposal.flags[1] = true; // processed
+
+            // the export fails if the state grew too large to be exported in one call
+            let did_pass = self._did_pass(proposal_id, &proposal)
+                && self._export_entries() <= constant::MAX_EXPORT_ENTRIES;
+
+            if did_pass {
+                proposal.flags[2] = true; // didPass
+            }
+
+            self.proposals.insert(proposal_id, proposal);
+            self._settle_deposit(
+                proposal_id,
+                proposal.sponsor.expect("proposal has no sponsor"),
+            );
+            // freeze the state once the deposit is settled, so that the export includes it
+            if did_pass {
+                self.successor = Some(successor);
+            }
+
+            self.env().emit_event(ProcessExportProposal {
+                proposal_index,
+                proposal_id,
+                did_pass,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to process a proposal removing the guardian.
+        #[ink(message)]
+        pub fn process_remove_guardian_proposal(
+            &mut self,
+            proposal_index: ProposalIndex,
+        ) -> Result<(), String> {
+            let proposal_id = self._validate_proposal_for_processing(proposal_index)?;
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            ensure!(
+                self.proposal_kinds.get(&proposal_id) == Some(&ProposalKind::RemoveGuardian),
+                "must be a remove guardian proposal"
+            );
+
+            proposal.flags[1] = true; // processed
+
+            let did_pass = self._did_pass(proposal_id, &proposal);
+
+            if did_pass {
+                proposal.flags[2] = true; // didPass
+                if let Some(guardian) = self.guardian.take() {
+                    self.env().emit_event(GuardianRemoved { guardian });
+                }
+            }
+
+            self.proposals.insert(proposal_id, proposal);
+            self._settle_deposit(
+                proposal_id,
+                proposal.sponsor.expect("proposal has no sponsor"),
+            );
+
+            self.env().emit_event(ProcessRemoveGuardianProposal {
+                proposal_index,
+                proposal_id,
+                did_pass,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call for the guardian to veto a sponsored proposal before it is processed.
+        ///
+        /// The proposal is marked as processed and failed, its tribute goes back to the proposer
+        /// and the whole deposit back to the sponsor. Proposals removing the guardian cannot be vetoed.
+        #[ink(message)]
+        pub fn veto_proposal(&mut self, proposal_index: ProposalIndex) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let caller = self.env().caller();
+            ensure!(self.guardian() == Some(caller), "not the guardian");
+            ensure!(
+                proposal_index < self.proposal_queue.len() as u128,
+                "proposal does not exist"
+            );
+            let proposal_id = self.proposal_queue[proposal_index as u32];
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            ensure!(!proposal.flags[1], "proposal has already been processed");
+            let kind = self.proposal_kinds.get(&proposal_id).cloned();
+            ensure!(
+                kind != Some(ProposalKind::RemoveGuardian),
+                "cannot veto the removal of the guardian"
+            );
+
+            proposal.flags[1] = true; // processed
+
+            // release the guards set when the proposal was sponsored
+            if proposal.flags[4] {
+                let token = proposal
+                    .tribute_token
+                    .expect("proposal has no tribute token");
+                self.proposed_to_whitelist.insert(token, false);
+            } else if proposal.flags[5] {
+                let applicant = proposal.applicant.expect("proposal has no applicant");
+                self.proposed_to_kick.insert(applicant, false);
+            } else if let Some(ProposalKind::Dewhitelist(token)) = kind {
+                self.proposed_to_dewhitelist.insert(token, false);
+            }
+
+            // return the tribute to the proposer
+            if let (Some(tribute_offered), Some(tribute_token)) =
+                (proposal.tribute_offered, proposal.tribute_token)
+            {
+                self.unsafe_internal_transfer(
+                    AccountId::from(ESCROW),
+                    proposal.proposer,
+                    tribute_token,
+                    tribute_offered,
+                );
+            }
+
+            // return the whole deposit to the sponsor, there is no processor to reward
+            let sponsor = proposal.sponsor.expect("proposal has no sponsor");
+            let proposal_deposit = self._proposal_parameters(proposal_id).proposal_deposit;
+            let deposit_token = self.deposit_token();
+            self.unsafe_internal_transfer(
+                AccountId::from(ESCROW),
+                sponsor,
+                deposit_token,
+                proposal_deposit,
+            );
+
+            self.proposals.insert(proposal_id, proposal);
+
+            self.env().emit_event(DepositSettled {
+                proposal_id,
+                sponsor,
+                processor: caller,
+                processing_reward: 0,
+                refunded: proposal_deposit,
+            });
+            self.env().emit_event(VetoProposal {
+                proposal_index,
+                proposal_id,
+                guardian: caller,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to vote for pausing or unpausing the Moloch.
+        ///
+        /// Pause votes are kept out of the proposal queue so that they stay available while
+        /// paused. The pause state switches as soon as the weight of the votes reaches
+        /// `PAUSE_SUPERMAJORITY_PERCENTAGE` of the total voting weight.
+        #[ink(message)]
+        pub fn submit_pause_vote(&mut self, pause: bool) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let caller = self.env().caller();
+            let member_address = *self
+                .member_address_by_delegate_key
+                .get(&caller)
+                .ok_or_else(|| String::from("not a delegate"))?;
+            let member = self.members(member_address).expect("member not found");
+            ensure!(member.shares > 0, "not a member with shares");
+            if pause {
+                ensure!(!self.paused, "already paused");
+            } else {
+                ensure!(self.paused, "not paused");
+            }
+            ensure!(
+                !*self
+                    .pause_votes
+                    .get(&(self.pause_round, member_address))
+                    .unwrap_or(&false),
+                "member has already voted"
+            );
+
+            let weight = self.voting_mode.weight(member.shares);
+            self.pause_votes
+                .insert((self.pause_round, member_address), true);
+            let mut pause_voters = self
+                .pause_voters
+                .get(&self.pause_round)
+                .cloned()
+                .unwrap_or_default();
+            pause_voters.push(member_address);
+            self.pause_voters.insert(self.pause_round, pause_voters);
+
+            self.env().emit_event(SubmitPauseVote {
+                member_address,
+                pause,
+                weight,
+            });
+
+            if self._pause_threshold_reached() {
+                self._set_paused(pause, member_address);
+            }
+            Ok(())
+        }
+
+        /// Defines a RPC call to switch the pause state once the votes of the current round
+        /// reach the threshold, which ragequits and guild kicks of other members can bring
+        /// about after the last vote.
+        #[ink(message)]
+        pub fn check_pause_threshold(&mut self) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            ensure!(
+                self._pause_threshold_reached(),
+                "pause votes below the threshold"
+            );
+            self._set_paused(!self.paused, self.env().caller());
+            Ok(())
+        }
+
+        /// Defines a RPC call for the guardian to pause the Moloch.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let caller = self.env().caller();
+            ensure!(self.guardian() == Some(caller), "not the guardian");
+            ensure!(!self.paused, "already paused");
+            self._set_paused(true, caller);
+            Ok(())
+        }
+
+        /// Defines a RPC call for the guardian to unpause the Moloch.
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let caller = self.env().caller();
+            ensure!(self.guardian() == Some(caller), "not the guardian");
+            ensure!(self.paused, "not paused");
+            self._set_paused(false, caller);
+            Ok(())
+        }
+
+        /// Defines a RPC call to get whether the Moloch is paused.
+        #[ink(message)]
+        pub fn paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Defines a RPC call to get the weight of the votes to switch the pause state.
+        ///
+        /// Votes weigh the shares their voters hold now, so that shares ragequit or kicked
+        /// since a vote no longer count.
+        #[ink(message)]
+        pub fn pause_votes_weight(&self) -> u128 {
+            self.pause_voters
+                .get(&self.pause_round)
+                .map_or(0, |pause_voters| {
+                    pause_voters
+                        .iter()
+                        .filter_map(|member_address| self.members(*member_address))
+                        .filter(|member| member.jailed == 0)
+                        .map(|member| self.voting_mode.weight(member.shares))
+                        .sum()
+                })
+        }
+
+        /// Defines a RPC call to get whether a member voted to switch the pause state.
+        #[ink(message)]
+        pub fn has_voted_pause(&self, member_address: AccountId) -> bool {
+            *self
+                .pause_votes
+                .get(&(self.pause_round, member_address))
+                .unwrap_or(&false)
+        }
+
+        /// Defines a RPC call to claim the shares and loot vested so far.
+        ///
+        /// Shares vested by a jailed member are claimed as loot, as they would have been
+        /// turned into loot by the guild kick.
+        #[ink(message)]
+        pub fn claim_vested(&mut self) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let member_address = self.env().caller();
+            let mut member = self
+                .members(member_address)
+                .ok_or_else(|| String::from("not a member"))?;
+            let current_period = self.get_current_period();
+
+            let mut shares_claimed = 0;
+            let mut loot_claimed = 0;
+            let mut grants = self
+                .vesting_grants
+                .get(&member_address)
+                .cloned()
+                .unwrap_or_default();
+            for grant in grants.iter_mut() {
+                let (shares, loot) = grant.claimable(current_period);
+                grant.claimed_shares += shares;
+                grant.claimed_loot += loot;
+                shares_claimed += shares;
+                loot_claimed += loot;
+            }
+            ensure!(
+                shares_claimed > 0 || loot_claimed > 0,
+                "nothing vested to claim"
+            );
+            grants.retain(|grant| !grant.is_claimed());
+            self.vesting_grants.insert(member_address, grants);
+
+            member.unvested_shares -= shares_claimed;
+            member.unvested_loot -= loot_claimed;
+            self.total_unvested -= shares_claimed + loot_claimed;
+
+            let (shares, loot) = if member.jailed != 0 {
+                (0, shares_claimed + loot_claimed)
+            } else {
+                (shares_claimed, loot_claimed)
+            };
+            let old_shares = member.shares;
+            member.shares += shares;
+            member.loot += loot;
+            self.members.insert(member_address, member);
+            self._update_total_voting_weight(old_shares, member.shares);
+            self.total_shares += shares;
+            self.total_loot += loot;
+            self._mint_companion_tokens(member_address, shares, loot);
+
+            self.env().emit_event(ClaimVested {
+                member_address,
+                shares,
+                loot,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to get the vesting schedule of a proposal, if any.
+        #[ink(message)]
+        pub fn proposal_vesting(&self, proposal_id: ProposalId) -> Option<VestingSchedule> {
+            self.proposal_vesting.get(&proposal_id).copied()
+        }
+
+        /// Defines a RPC call to get the shares and loot granted to a member and not fully claimed yet.
+        #[ink(message)]
+        pub fn vesting_grants(&self, member_address: AccountId) -> Vec<VestingGrant> {
+            self.vesting_grants
+                .get(&member_address)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        /// Defines a RPC call to get the shares and loot a member can claim now.
+        #[ink(message)]
+        pub fn claimable_vested(&self, member_address: AccountId) -> (u128, u128) {
+            let current_period = self.get_current_period();
+            self.vesting_grants(member_address)
+                .iter()
+                .map(|grant| grant.claimable(current_period))
+                .fold((0, 0), |(shares, loot), (grant_shares, grant_loot)| {
+                    (shares + grant_shares, loot + grant_loot)
+                })
+        }
+
+        /// Defines a RPC call to get the guardian, if any and its role has not expired.
+        #[ink(message)]
+        pub fn guardian(&self) -> Option<AccountId> {
+            self.guardian
+                .filter(|_| self.get_current_period() < self.guardian_expiry_period)
+        }
+
+        /// Defines a RPC call to get the period from which the guardian can no longer veto proposals.
+        #[ink(message)]
+        pub fn guardian_expiry_period(&self) -> u128 {
+            self.guardian_expiry_period
+        }
+
+        /// Defines a RPC call to get the deployment the state is exported to, if a proposal
+        /// approved the export.
+        /// The selector matches `SUCCESSOR_SELECTOR`.
+        #[ink(message)]
+        #[ink(selector = "0x3e6b0d53")]
+        pub fn successor(&self) -> Option<AccountId> {
+            self.successor
+        }
+
+        /// Defines a RPC call to get the deployment whose state this Moloch is waiting to import.
+        #[ink(message)]
+        pub fn predecessor(&self) -> Option<AccountId> {
+            self.predecessor
+        }
+
+        /// Defines a RPC call to export the state, once a proposal approved it.
+        ///
+        /// The Moloch is frozen from then on, so the export keeps matching the state the
+        /// members approved. Processed proposals are only exported while they are in the
+        /// queue, which keeps the proposal indices of the rebuilt Moloch unchanged.
+        /// The selector matches `EXPORT_STATE_SELECTOR`.
+        #[ink(message)]
+        #[ink(selector = "0x3e6b0d52")]
+        pub fn export_state(&self) -> Result<StateSnapshot, String> {
+            ensure!(
+                self.successor.is_some(),
+                "state export has not been approved"
+            );
+
+            let proposals = self
+                .proposals
+                .iter()
+                .filter(|(_, proposal)| proposal.flags[0] || !proposal.flags[3])
+                .map(|(proposal_id, proposal)| ProposalSnapshot {
+                    proposal_id: *proposal_id,
+                    proposal: *proposal,
+                    kind: self.proposal_kinds.get(proposal_id).cloned(),
+                    parameters: self.proposal_parameters.get(proposal_id).copied(),
+                    vesting: self.proposal_vesting.get(proposal_id).copied(),
+                })
+                .collect();
+            let votes = self
+                .votes_by_member
+                .iter()
+                .filter(|((proposal_id, _), _)| {
+                    self.proposals(*proposal_id)
+                        .map_or(false, |proposal| !proposal.flags[1])
+                })
+                .map(|(key, vote)| (*key, *vote))
+                .collect();
+            let delegated_votes = self
+                .delegated_votes
+                .iter()
+                .filter(|((proposal_id, _), _)| {
+                    self.proposals(*proposal_id)
+                        .map_or(false, |proposal| !proposal.flags[1])
+                })
+                .map(|(key, delegated_vote)| (*key, *delegated_vote))
+                .collect();
+
+            Ok(StateSnapshot {
+                version: self.storage_version,
+                summoning_time: self.summoning_time,
+                period_duration: self.period_duration,
+                parameters: self._current_parameters(),
+                quorum_percentage: self.quorum_percentage,
+                supermajority_percentage: self.supermajority_percentage,
+                voting_mode: self.voting_mode,
+                approved_tokens: self.approved_tokens.iter().copied().collect(),
+                delisted_tokens: self.delisted_tokens.iter().copied().collect(),
+                members: self
+                    .members
+                    .iter()
+                    .map(|(member_address, member)| (*member_address, *member))
+                    .collect(),
+                user_token_balances: self
+                    .user_token_balances
+                    .iter()
+                    .filter(|(_, balance)| **balance > 0)
+                    .map(|(key, balance)| (*key, *balance))
+                    .collect(),
+                total_guild_bank_tokens: self.total_guild_bank_tokens,
+                proposal_count: self.proposal_count,
+                proposal_queue: self.proposal_queue.iter().copied().collect(),
+                proposals,
+                votes,
+                ballots: self
+                    .ballots
+                    .iter()
+                    .filter(|(proposal_id, _)| {
+                        self.proposals(**proposal_id)
+                            .map_or(false, |proposal| !proposal.flags[1])
+                    })
+                    .map(|(proposal_id, ballots)| (*proposal_id, ballots.clone()))
+                    .collect(),
+                vote_delegates: self
+                    .vote_delegates
+                    .iter()
+                    .map(|(delegator, delegate)| (*delegator, *delegate))
+                    .collect(),
+                delegated_votes,
+                conviction_proposal_count: self.conviction_proposal_count,
+                conviction_proposals: self
+                    .conviction_proposals
+                    .iter()
+                    .map(|(conviction_proposal_id, proposal)| (*conviction_proposal_id, *proposal))
+                    .collect(),
+                conviction_stakes: self
+                    .conviction_stakes
+                    .iter()
+                    .filter(|(_, stake)| **stake > 0)
+                    .map(|(key, stake)| (*key, *stake))
+                    .collect(),
+                guardian: self.guardian,
+                guardian_expiry_period: self.guardian_expiry_period,
+                paused: self.paused,
+                vesting_grants: self
+                    .vesting_grants
+                    .iter()
+                    .filter(|(_, grants)| !grants.is_empty())
+                    .map(|(member_address, grants)| (*member_address, grants.clone()))
+                    .collect(),
+            })
+        }
+
+        /// Defines a RPC call to rebuild the state exported by the predecessor, once its
+        /// members approved an export proposal naming this contract.
+        ///
+        /// Companion tokens are not carried over. The imported balances are only backed by
+        /// tokens held by this contract once `migrate_funds` of the predecessor moved them.
+        #[ink(message)]
+        pub fn import_state(&mut self) -> Result<(), String> {
+            let predecessor = self
+                .predecessor
+                .ok_or_else(|| String::from("no state to import"))?;
+            let successor = build_call::<Environment>()
+                .callee(predecessor)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(ExecutionInput::new(Selector::new(SUCCESSOR_SELECTOR)))
+                .returns::<ReturnType<Option<AccountId>>>()
+                .fire()
+                .map_err(|_| String::from("predecessor call failed"))?;
+            ensure!(
+                successor == Some(self.env().account_id()),
+                "the predecessor did not approve an export to this contract"
+            );
+            let snapshot = build_call::<Environment>()
+                .callee(predecessor)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(ExecutionInput::new(Selector::new(EXPORT_STATE_SELECTOR)))
+                .returns::<ReturnType<Result<StateSnapshot, String>>>()
+                .fire()
+                .map_err(|_| String::from("predecessor call failed"))??;
+            ensure!(
+                snapshot.version == STORAGE_VERSION,
+                "unsupported snapshot version"
+            );
+            self.predecessor = None;
+
+            self._import_snapshot(snapshot);
+
+            self.env().emit_event(ImportState { predecessor });
+            Ok(())
+        }
+
+        /// Defines a RPC call to move the balance of a token held by the frozen Moloch to the
+        /// successor, whose imported internal balances account for it.
+        #[ink(message)]
+        pub fn migrate_funds(&mut self, token: AccountId) -> Result<(), String> {
+            let successor = self
+                .successor
+                .ok_or_else(|| String::from("state export has not been approved"))?;
+            ensure!(
+                !*self.migrated_tokens.get(&token).unwrap_or(&false),
+                "token has already been migrated"
+            );
+            let amount = self.user_token_balances(AccountId::from(TOTAL), token);
+            ensure!(amount > 0, "no balance to migrate");
+
+            let transferred = if token == AccountId::from(NATIVE) {
+                self.env().transfer(successor, amount).is_ok()
+            } else {
+                let mut erc20: Erc20 = Erc20::from_account_id(token);
+                erc20.transfer(successor, amount, Vec::new()).is_ok()
+            };
+            ensure!(transferred, "token transfer failed");
+            self.migrated_tokens.insert(token, true);
+
+            self.env().emit_event(MigrateFunds {
+                successor,
+                token,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to submit a funding proposal to conviction voting.
+        #[ink(message)]
+        pub fn submit_conviction_proposal(
+            &mut self,
+            applicant: AccountId,
+            payment_requested: u128,
+            payment_token: AccountId,
+            details: String,
+        ) -> Result<ConvictionProposalId, String> {
+            self._ensure_not_paused()?;
+            ensure!(
+                *self.token_whitelist.get(&payment_token).unwrap_or(&false),
+                "payment is not whitelisted"
+            );
+            ensure!(payment_requested > 0, "must request a payment");
+            ensure!(applicant != AccountId::default(), "applicant cannot be 0");
+            ensure!(
+                applicant != AccountId::from(GUILD)
+                    && applicant != AccountId::from(ESCROW)
+                    && applicant != AccountId::from(TOTAL),
+                "applicant address cannot be reserved"
+            );
+
+            let conviction_proposal_id = self.conviction_proposal_count;
+            self.conviction_proposals.insert(
+                conviction_proposal_id,
+                ConvictionProposal {
+                    proposer: self.env().caller(),
+                    applicant,
+                    payment_requested,
+                    payment_token,
+                    staked: 0,
+                    conviction: 0,
+                    last_period: self.get_current_period(),
+                    executed: false,
+                    details: self.env().hash_bytes::<Blake2x256>(details.as_bytes()),
+                },
+            );
+            self.conviction_proposal_count += 1;
+
+            self.env().emit_event(SubmitConvictionProposal {
+                conviction_proposal_id,
+                applicant,
+                payment_requested,
+                payment_token,
+                details,
+            });
+            Ok(conviction_proposal_id)
+        }
+
+        /// Defines a RPC call to stake shares on a conviction proposal.
+        ///
+        /// Staked shares keep their voting power in the proposal queue but cannot be ragequit.
+        #[ink(message)]
+        pub fn stake_conviction(
+            &mut self,
+            conviction_proposal_id: ConvictionProposalId,
+            amount: u128,
+        ) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let caller = self.env().caller();
+            let member_address = self.member_address_by_delegate_key(caller);
+            ensure!(
+                member_address
+                    .and_then(|member_address| self.members(member_address))
+                    .map_or(false, |member| member.shares > 0),
+                "not a delegate"
+            );
+            let member_address = member_address.unwrap();
+            let member = self.members(member_address).unwrap();
+            ensure!(member.jailed == 0, "member is jailed");
+            let mut proposal = self
+                .conviction_proposals(conviction_proposal_id)
+                .ok_or_else(|| String::from("proposal does not exist"))?;
+            ensure!(!proposal.executed, "proposal has already been executed");
+            ensure!(amount > 0, "must stake at least one share");
+            let staked_shares = self.staked_shares(member_address);
+            ensure!(
+                member.shares >= staked_shares + amount,
+                "insufficient unstaked shares"
+            );
+            let mut staked_proposals = self
+                .staked_proposals
+                .get(&member_address)
+                .cloned()
+                .unwrap_or_default();
+            if !staked_proposals.contains(&conviction_proposal_id) {
+                ensure!(
+                    (staked_proposals.len() as u128) < constant::MAX_STAKED_PROPOSALS,
+                    "staked on too many proposals"
+                );
+                staked_proposals.push(conviction_proposal_id);
+            }
+
+            let current_period = self.get_current_period();
+            proposal.conviction = proposal.conviction_at(current_period);
+            proposal.last_period = current_period;
+            proposal.staked += amount;
+            self.conviction_proposals
+                .insert(conviction_proposal_id, proposal);
+
+            let stake = self.conviction_stake(conviction_proposal_id, member_address) + amount;
+            self.conviction_stakes
+                .insert((conviction_proposal_id, member_address), stake);
+            self.staked_shares
+                .insert(member_address, staked_shares + amount);
+            self.staked_proposals
+                .insert(member_address, staked_proposals);
+
+            self.env().emit_event(StakeConviction {
+                conviction_proposal_id,
+                member_address,
+                amount,
+                staked: proposal.staked,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to withdraw shares staked on a conviction proposal.
+        #[ink(message)]
+        pub fn unstake_conviction(
+            &mut self,
+            conviction_proposal_id: ConvictionProposalId,
+            amount: u128,
+        ) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let caller = self.env().caller();
+            let member_address = self
+                .member_address_by_delegate_key(caller)
+                .unwrap_or(caller);
+            let stake = self.conviction_stake(conviction_proposal_id, member_address);
+            ensure!(stake >= amount, "insufficient stake");
+            let mut proposal = self
+                .conviction_proposals(conviction_proposal_id)
+                .expect("proposal not found");
+
+            if !proposal.executed {
+                let current_period = self.get_current_period();
+                proposal.conviction = proposal.conviction_at(current_period);
+                proposal.last_period = current_period;
+            }
+            proposal.staked -= amount;
+            self.conviction_proposals
+                .insert(conviction_proposal_id, proposal);
+
+            self.conviction_stakes
+                .insert((conviction_proposal_id, member_address), stake - amount);
+            let staked_shares = self.staked_shares(member_address);
+            self.staked_shares
+                .insert(member_address, staked_shares - amount);
+            if stake == amount {
+                if let Some(staked_proposals) = self.staked_proposals.get_mut(&member_address) {
+                    staked_proposals.retain(|staked| *staked != conviction_proposal_id);
+                }
+            }
+
+            self.env().emit_event(UnstakeConviction {
+                conviction_proposal_id,
+                member_address,
+                amount,
+                staked: proposal.staked,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to pay a conviction proposal whose conviction crossed its threshold.
+        #[ink(message)]
+        pub fn execute_conviction_proposal(
+            &mut self,
+            conviction_proposal_id: ConvictionProposalId,
+        ) -> Result<(), String> {
+            self._ensure_not_paused()?;
+            let mut proposal = self
+                .conviction_proposals(conviction_proposal_id)
+                .ok_or_else(|| String::from("proposal does not exist"))?;
+            ensure!(!proposal.executed, "proposal has already been executed");
+            ensure!(
+                self.token_whitelist(proposal.payment_token),
+                "payment is not whitelisted"
+            );
+
+            let current_period = self.get_current_period();
+            let conviction = proposal.conviction_at(current_period);
+            let threshold = self
+                .conviction_threshold(conviction_proposal_id)
+                .ok_or_else(|| String::from("payment exceeds the spending limit"))?;
+            ensure!(conviction >= threshold, "not enough conviction");
+
+            proposal.conviction = conviction;
+            proposal.last_period = current_period;
+            proposal.executed = true;
+            self.conviction_proposals
+                .insert(conviction_proposal_id, proposal);
+
+            let guild = AccountId::from(GUILD);
+            self.unsafe_internal_transfer(
+                guild,
+                proposal.applicant,
+                proposal.payment_token,
+                proposal.payment_requested,
+            );
+            // if the proposal spends 100% of guild bank balance for a token, decrement total guild bank tokens
+            if self.user_token_balances(guild, proposal.payment_token) == 0 {
+                self.total_guild_bank_tokens -= 1;
+            }
+
+            self.env().emit_event(ExecuteConvictionProposal {
+                conviction_proposal_id,
+                applicant: proposal.applicant,
+                payment_requested: proposal.payment_requested,
+                conviction,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to get the conviction a proposal needs to be executed, none if
+        /// it requests more than the spending limit of the guild bank.
+        #[ink(message)]
+        pub fn conviction_threshold(
+            &self,
+            conviction_proposal_id: ConvictionProposalId,
+        ) -> Option<u128> {
+            let proposal = self.conviction_proposals(conviction_proposal_id)?;
+            conviction::threshold(
+                proposal.payment_requested,
+                self.user_token_balances(AccountId::from(GUILD), proposal.payment_token),
+                self.total_shares,
+            )
+        }
+
+        /// Defines a RPC call to get the current conviction of a proposal.
+        #[ink(message)]
+        pub fn conviction(&self, conviction_proposal_id: ConvictionProposalId) -> Option<u128> {
+            self.conviction_proposals(conviction_proposal_id)
+                .map(|proposal| proposal.conviction_at(self.get_current_period()))
+        }
+
+        /// Defines a RPC call to process a multi-option proposal.
+        ///
+        /// The weight of each option is recorded in an `OptionTally` event, the proposal fails
+        /// without a winner or if the guild bank cannot make the payment of the winner.
+        #[ink(message)]
+        pub fn process_multi_option_proposal(
+            &mut self,
+            proposal_index: ProposalIndex,
+        ) -> Result<(), String> {
+            let proposal_id = self._validate_proposal_for_processing(proposal_index)?;
+            let mut proposal = self.proposals(proposal_id).expect("proposal not found");
+            let multi_option = match self.proposal_kinds.get(&proposal_id) {
+                Some(ProposalKind::MultiOption(multi_option)) => multi_option.clone(),
+                _ => return Err(String::from("must be a multi-option proposal")),
+            };
+
+            proposal.flags[1] = true; // processed
+
+            let mut did_pass = self._did_pass(proposal_id, &proposal);
+
+            // ballots weigh the shares their voters hold now, not when they voted
+            let ballots: Vec<Ballot> = self
+                .ballots(proposal_id)
+                .into_iter()
+                .map(|mut ballot| {
+                    ballot.weight = self
+                        .members(ballot.member_address)
+                        .filter(|member| member.jailed == 0)
+                        .map_or(0, |member| self.voting_mode.weight(member.shares));
+                    ballot
+                })
+                .collect();
+            let (weights, winner) =
+                multi_option::tally(multi_option.method, multi_option.options.len(), &ballots);
+            let payment = winner.and_then(|option| multi_option.options[option as usize]);
+
+            // Make the proposal fail if no option won
+            if winner.is_none() {
+                did_pass = false;
+            }
+
+            // Make the proposal fail if the guild bank cannot make the payment of the winner
+            if let Some(payment) = payment {
+                if !self.token_whitelist(payment.payment_token)
+                    || payment.payment_requested
+                        > self.user_token_balances(AccountId::from(GUILD), payment.payment_token)
+                {
+                    did_pass = false;
+                }
+            }
+
+            if did_pass {
+                proposal.flags[2] = true; // didPass
+
+                if let Some(payment) = payment {
+                    let guild = AccountId::from(GUILD);
+                    self.unsafe_internal_transfer(
+                        guild,
+                        payment.applicant,
+                        payment.payment_token,
+                        payment.payment_requested,
+                    );
+
+                    // if the proposal spends 100% of guild bank balance for a token, decrement total guild bank tokens
+                    if self.user_token_balances(guild, payment.payment_token) == 0
+                        && payment.payment_requested > 0
+                    {
+                        self.total_guild_bank_tokens -= 1;
+                    }
+                }
+            }
+
+            self.proposals.insert(proposal_id, proposal);
+            self._settle_deposit(
+                proposal_id,
+                proposal.sponsor.expect("proposal has no sponsor"),
+            );
+
+            for (option, weight) in weights.into_iter().enumerate() {
+                self.env().emit_event(OptionTally {
+                    proposal_id,
+                    option: option as u8,
+                    weight,
+                });
+            }
+            self.env().emit_event(ProcessMultiOptionProposal {
+                proposal_index,
+                proposal_id,
+                did_pass,
+                winner,
+            });
+            Ok(())
+        }
+
+        /// Defines a RPC call to check if the member can ragequit.
+        #[ink(message)]
+        pub fn can_ragequit(&self, highest_index_yes_vote: ProposalIndex) -> Result<bool, String> {
+            ensure!(
+                highest_index_yes_vote < self.proposal_queue.len() as u128,
+                "proposal does not exist"
+            );
+            Ok(self
+                .proposal_queue(highest_index_yes_vote)
+                .and_then(|proposal_id| self.proposals(proposal_id))
+                .map_or(false, |proposal| proposal.flags[1]))
+        }
+
+        /// Defines a RPC call to ragequit.
+        #[ink(message)]
+        pub fn ragequit(&mut self, shares_to_burn: u128, loot_to_burn: u128) -> Result<(), String> {
+            let caller = self.env().caller();
+            ensure!(
+                self.members(caller)
+                    .map_or(false, |member| member.shares > 0 || member.loot > 0),
+                "not a member"
+            );
+            self._ragequit(caller, shares_to_burn, loot_to_burn)
+        }
+
+        /// Defines a RPC call to ragekick.
+        #[ink(message)]
+        pub fn ragekick(&mut self, member_to_kick: AccountId) -> Result<(), String> {
+            ensure!(
+                self.members(member_to_kick)
+                    .map_or(false, |member| member.jailed != 0),
+                "member must be in jail"
+            );
+            let member = self.members(member_to_kick).unwrap();
+            // note - should be impossible for jailed member to have shares
+            ensure!(member.loot > 0, "member must have some loot");
+            self._ragequit(member_to_kick, 0, member.loot)
+        }
+
+        /// Defines a RPC call to withdraw a single token balance.
+        ///
+        /// Balances of the native currency are paid out with a plain transfer.
+        #[ink(message)]
+        pub fn withdraw_balance(&mut self, token: AccountId, amount: u128) -> Result<(), String> {
+            self._ensure_not_paused()?;
+            ensure!(
+                self.user_token_balances(self.env().caller(), token) >= amount,
+                "insufficient balance"
+            );
+            self._withdraw_balance(token, amount)
+        }
+
+        /// Defines a RPC call to withdraw multiple token balances at once.
+        #[ink(message)]
+        pub fn withdraw_balances(
+            &mut self,
+            tokens: Vec<AccountId>,
+            amounts: Vec<u128>,
+        ) -> Result<(), String> {
+            self._ensure_not_paused()?;
+            ensure!(
+                tokens.len() == amounts.len(),
+                "tokens and amounts arrays must be matching lengths"
+            );
+            let caller = self.env().caller();
+            // check all balances first so that either all or none are withdrawn
+            for (i, token) in tokens.iter().enumerate() {
+                let requested: u128 = tokens
+                    .iter()
+                    .zip(amounts.iter())
+                    .take(i + 1)
+                    .filter(|(other, _)| *other == token)
+                    .map(|(_, amount)| *amount)
+                    .sum();
+                ensure!(
+                    self.user_token_balances(caller, *token) >= requested,
+                    "insufficient balance"
+                );
+            }
+            for (token, amount) in tokens.into_iter().zip(amounts.into_iter()) {
+                self._withdraw_balance(token, amount)?;
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn collect_tokens(&self, _token: AccountId) -> bool {
+            unimplemented!()
+        }
+
+        /// Defines a RPC call for the transferable loot token to move loot between members.
+        ///
+        /// A recipient who is not a member yet becomes a member without shares.
+        /// The selector matches `erc20::ON_COMPANION_TRANSFER_SELECTOR`.
+        #[ink(message)]
+        #[ink(selector = "0x5bf68d11")]
+        pub fn on_companion_transfer(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: u128,
+        ) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            ensure!(
+                self.loot_token == Some(self.env().caller()),
+                "caller is not the loot token"
+            );
+            ensure!(
+                to != AccountId::from(GUILD)
+                    && to != AccountId::from(ESCROW)
+                    && to != AccountId::from(TOTAL),
+                "recipient address cannot be reserved"
+            );
+            let mut sender = match self.members(from) {
+                Some(member) => member,
+                None => return Err(String::from("not a member")),
+            };
+            ensure!(sender.loot >= value, "insufficient loot");
+            ensure!(sender.jailed == 0, "member must not be jailed");
+            // loot leaving a member could be ragequit by the recipient, so it is locked like ragequit
+            ensure!(
+                self.proposal_queue.len() == 0 || self.can_ragequit(sender.highest_index_yes_vote)?,
+                "cannot transfer loot until highest index proposal member voted YES on is processed"
+            );
+            let recipient_is_member = self.members.contains_key(&to);
+            ensure!(
+                recipient_is_member || self.member_address_by_delegate_key(to).is_none(),
+                "cannot overwrite existing delegate keys"
+            );
+            sender.loot -= value;
+            self.members.insert(from, sender);
+
+            match self.members.get_mut(&to) {
+                Some(recipient) => recipient.loot += value,
+                None => {
+                    let mut member = Member::new(to);
+                    member.shares = 0;
+                    member.loot = value;
+                    self.members.insert(to, member);
+                    self.member_address_by_delegate_key.insert(to, to);
+                }
+            }
+            Ok(())
+        }
+
+        /// Defines a RPC call to be notified by a token of a transfer to this contract.
+        ///
+        /// `data` is a SCALE-encoded `Option<ProposalId>`: the tokens are credited to the
+        /// guild bank for `None`, or added to the tribute held in escrow of an unsponsored
+        /// proposal of `from`. Tokens transferred without data are not notified, and
+        /// not credited.
+        /// The selector matches `erc20::ON_TOKENS_RECEIVED_SELECTOR`.
+        #[ink(message)]
+        #[ink(selector = "0xa497139d")]
+        pub fn on_tokens_received(
+            &mut self,
+            operator: AccountId,
+            from: AccountId,
+            value: Balance,
+            data: Vec<u8>,
+        ) -> Result<(), String> {
+            // tribute and deposits collected by the Moloch itself are accounted for by the caller
+            if operator == self.env().account_id() {
+                return Ok(());
+            }
+            self._ensure_not_exported()?;
+            let token = self.env().caller();
+            ensure!(self.token_whitelist(token), "token is not whitelisted");
+
+            let proposal_id = <Option<ProposalId> as scale::Decode>::decode(&mut &data[..])
+                .map_err(|_| String::from("invalid proposal id"))?;
+            let proposal_id = match proposal_id {
+                Some(proposal_id) => proposal_id,
+                None => {
+                    if value > 0 && self.user_token_balances(AccountId::from(GUILD), token) == 0 {
+                        ensure!(
+                            self.total_guild_bank_tokens < constant::MAX_TOKEN_GUILDBANK_COUNT,
+                            "guildbank is full"
+                        );
+                        self.total_guild_bank_tokens += 1;
+                    }
+                    self.unsafe_add_to_balance(AccountId::from(GUILD), token, value);
+                    self.env().emit_event(TokensReceived {
+                        token,
+                        from,
+                        value,
+                        proposal_id: None,
+                    });
+                    return Ok(());
+                }
+            };
+            let mut proposal = match self.proposals.get(&proposal_id) {
+                Some(proposal) => *proposal,
+                None => return Err(String::from("proposal does not exist")),
+            };
+            ensure!(
+                proposal.proposer == from,
+                "only the proposer can add tribute"
+            );
+            ensure!(!proposal.flags[0], "proposal has already been sponsored");
+            ensure!(!proposal.flags[3], "proposal has been cancelled");
+            ensure!(
+                proposal.tribute_token == Some(token),
+                "token is not the tribute token of the proposal"
+            );
+            proposal.tribute_offered = Some(proposal.tribute_offered.unwrap_or(0) + value);
+            self.proposals.insert(proposal_id, proposal);
+            self.unsafe_add_to_balance(AccountId::from(ESCROW), token, value);
+            self.env().emit_event(TokensReceived {
+                token,
+                from,
+                value,
+                proposal_id: Some(proposal_id),
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn cancel_proposal(&self, _proposal_id: ProposalId) -> bool {
+            unimplemented!()
+        }
+
+        #[ink(message)]
+        pub fn update_delegate_key(&self, _new_delegate_key: AccountId) -> bool {
+            unimplemented!()
+        }
+
+        #[ink(message)]
+        pub fn get_proposal_queue_length(&self) -> u128 {
+            self.proposal_queue.len() as u128
+        }
+
+        #[ink(message)]
+        pub fn proposal_queue(&self, proposal_index: ProposalIndex) -> Option<ProposalId> {
+            self.proposal_queue.get(proposal_index as u32).copied()
+        }
+
+        #[ink(message)]
+        pub fn get_member_proposal_vote(
+            &self,
+            member_address: AccountId,
+            proposal_index: ProposalIndex,
+        ) -> Vote {
+            self.proposal_queue(proposal_index)
+                .and_then(|proposal_id| {
+                    self.votes_by_member
+                        .get(&(proposal_id, member_address))
+                        .copied()
+                })
+                .unwrap_or(Vote::None)
+        }
+
+        #[ink(message)]
+        pub fn conviction_proposals(
+            &self,
+            conviction_proposal_id: ConvictionProposalId,
+        ) -> Option<ConvictionProposal> {
+            self.conviction_proposals
+                .get(&conviction_proposal_id)
+                .copied()
+        }
+
+        #[ink(message)]
+        pub fn conviction_proposal_count(&self) -> u128 {
+            self.conviction_proposal_count
+        }
+
+        #[ink(message)]
+        pub fn conviction_stake(
+            &self,
+            conviction_proposal_id: ConvictionProposalId,
+            member_address: AccountId,
+        ) -> u128 {
+            self.conviction_stakes
+                .get(&(conviction_proposal_id, member_address))
+                .copied()
+                .unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn staked_shares(&self, member_address: AccountId) -> u128 {
+            self.staked_shares
+                .get(&member_address)
+                .copied()
+                .unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn voting_mode(&self) -> VotingMode {
+            self.voting_mode
+        }
+
+        #[ink(message)]
+        pub fn ballots(&self, proposal_id: ProposalId) -> Vec<Ballot> {
+            self.ballots.get(&proposal_id).cloned().unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn vote_delegate(&self, member_address: AccountId) -> Option<AccountId> {
+            self.vote_delegates.get(&member_address).copied()
+        }
+
+        #[ink(message)]
+        pub fn vote_delegators(&self, member_address: AccountId) -> Vec<AccountId> {
+            self.vote_delegators
+                .get(&member_address)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_proposal_flags(&self, proposal_id: ProposalId) -> Option<[bool; 6]> {
+            match self.proposals(proposal_id) {
+                Some(proposal) => Some(proposal.flags),
+                None => None,
+            }
+        }
+
+        #[ink(message)]
+        pub fn proposal_kind(&self, proposal_id: ProposalId) -> Option<ProposalKind> {
+            self.proposal_kinds.get(&proposal_id).cloned()
+        }
+
+        #[ink(message)]
+        pub fn proposal_parameters(&self, proposal_id: ProposalId) -> Option<Parameters> {
+            self.proposal_parameters.get(&proposal_id).copied()
+        }
+
+        #[ink(message)]
+        pub fn user_token_balances(&self, user: AccountId, token: AccountId) -> Balance {
+            *self.user_token_balances.get(&(user, token)).unwrap_or(&0)
         }
 
         /***************
@@ -815,5 +3574,537 @@ mod submoloch {
                 .and_modify(|old_value| *old_value += amount)
                 .or_insert(amount);
         }
+
+        fn unsafe_subtract_from_balance(
+            &mut self,
+            user: AccountId,
+            token: AccountId,
+            amount: Balance,
+        ) {
+            self.user_token_balances
+                .entry((user, token))
+                .and_modify(|old_value| *old_value -= amount)
+                .or_insert(0);
+            self.user_token_balances
+                .entry((AccountId::from(TOTAL), token))
+                .and_modify(|old_value| *old_value -= amount)
+                .or_insert(0);
+        }
+
+        fn unsafe_internal_transfer(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            token: AccountId,
+            amount: Balance,
+        ) {
+            self.unsafe_subtract_from_balance(from, token, amount);
+            self.unsafe_add_to_balance(to, token, amount);
+        }
+
+        fn _mint_companion_tokens(&self, member: AccountId, shares: u128, loot: u128) {
+            if let Some(token) = self.shares_token.filter(|_| shares > 0) {
+                let mut token: Erc20 = Erc20::from_account_id(token);
+                token
+                    .mint(member, shares)
+                    .expect("failed at minting shares tokens");
+            }
+            if let Some(token) = self.loot_token.filter(|_| loot > 0) {
+                let mut token: Erc20 = Erc20::from_account_id(token);
+                token
+                    .mint(member, loot)
+                    .expect("failed at minting loot tokens");
+            }
+        }
+
+        fn _burn_companion_tokens(&self, member: AccountId, shares: u128, loot: u128) {
+            if let Some(token) = self.shares_token.filter(|_| shares > 0) {
+                let mut token: Erc20 = Erc20::from_account_id(token);
+                token
+                    .burn_from(member, shares)
+                    .expect("failed at burning shares tokens");
+            }
+            if let Some(token) = self.loot_token.filter(|_| loot > 0) {
+                let mut token: Erc20 = Erc20::from_account_id(token);
+                token
+                    .burn_from(member, loot)
+                    .expect("failed at burning loot tokens");
+            }
+        }
+
+        fn _update_total_voting_weight(&mut self, old_shares: u128, new_shares: u128) {
+            self.total_voting_weight = self.total_voting_weight
+                + self.voting_mode.weight(new_shares)
+                - self.voting_mode.weight(old_shares);
+        }
+
+        /// Withdraws every stake of a member from the conviction proposals.
+        fn _unstake_all_conviction(&mut self, member_address: AccountId) {
+            let current_period = self.get_current_period();
+            let staked_proposals = self
+                .staked_proposals
+                .take(&member_address)
+                .unwrap_or_default();
+            for conviction_proposal_id in staked_proposals {
+                let stake = self
+                    .conviction_stakes
+                    .take(&(conviction_proposal_id, member_address))
+                    .unwrap_or(0);
+                let mut proposal = self
+                    .conviction_proposals(conviction_proposal_id)
+                    .expect("proposal not found");
+                if !proposal.executed {
+                    proposal.conviction = proposal.conviction_at(current_period);
+                    proposal.last_period = current_period;
+                }
+                proposal.staked -= stake;
+                self.conviction_proposals
+                    .insert(conviction_proposal_id, proposal);
+
+                self.env().emit_event(UnstakeConviction {
+                    conviction_proposal_id,
+                    member_address,
+                    amount: stake,
+                    staked: proposal.staked,
+                });
+            }
+            self.staked_shares.take(&member_address);
+        }
+
+        /// The weight already counted by the delegate stays on its votes, and keeps the delegator
+        /// from being counted again by a new delegate on the same proposals.
+        fn _revoke_vote_delegate(&mut self, delegator: AccountId) -> Option<AccountId> {
+            let delegate = self.vote_delegates.take(&delegator)?;
+            if let Some(delegators) = self.vote_delegators.get_mut(&delegate) {
+                delegators.retain(|address| *address != delegator);
+            }
+            Some(delegate)
+        }
+
+        fn _ragequit(
+            &mut self,
+            member_address: AccountId,
+            shares_to_burn: u128,
+            loot_to_burn: u128,
+        ) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            let initial_total_shares_and_loot = self.total_shares + self.total_loot;
+            let mut member = self.members(member_address).expect("member not found");
+
+            // unvested shares & loot are only added to the member once claimed, so they cannot be ragequit
+            ensure!(member.shares >= shares_to_burn, "insufficient shares");
+            ensure!(member.loot >= loot_to_burn, "insufficient loot");
+            ensure!(
+                shares_to_burn == 0
+                    || member.shares - shares_to_burn >= self.staked_shares(member_address),
+                "cannot ragequit staked shares"
+            );
+            ensure!(
+                self.can_ragequit(member.highest_index_yes_vote)?,
+                "cannot ragequit until highest index proposal member voted YES on is processed"
+            );
+
+            let shares_and_loot_to_burn = shares_to_burn + loot_to_burn;
+
+            // burn shares and loot
+            self._update_total_voting_weight(member.shares, member.shares - shares_to_burn);
+            member.shares -= shares_to_burn;
+            member.loot -= loot_to_burn;
+            self.members.insert(member_address, member);
+            self.total_shares -= shares_to_burn;
+            self.total_loot -= loot_to_burn;
+            self._burn_companion_tokens(member_address, shares_to_burn, loot_to_burn);
+
+            let guild = AccountId::from(GUILD);
+            let tokens: Vec<AccountId> = self
+                .approved_tokens
+                .iter()
+                .chain(self.delisted_tokens.iter())
+                .copied()
+                .collect();
+            for token in tokens {
+                let amount_to_ragequit = utils::fair_share(
+                    self.user_token_balances(guild, token),
+                    shares_and_loot_to_burn,
+                    initial_total_shares_and_loot,
+                );
+                if amount_to_ragequit > 0 {
+                    self.unsafe_internal_transfer(guild, member_address, token, amount_to_ragequit);
+                    if self.user_token_balances(guild, token) == 0 {
+                        self.total_guild_bank_tokens -= 1;
+                    }
+                }
+            }
+
+            // forget the delisted tokens the guild bank no longer holds
+            let mut index = self.delisted_tokens.len();
+            while index > 0 {
+                index -= 1;
+                if self.user_token_balances(guild, self.delisted_tokens[index]) == 0 {
+                    self.delisted_tokens.swap_remove_drop(index);
+                }
+            }
+
+            self.env().emit_event(Ragequit {
+                member_address,
+                shares_to_burn,
+                loot_to_burn,
+            });
+            Ok(())
+        }
+
+        fn _withdraw_balance(&mut self, token: AccountId, amount: u128) -> Result<(), String> {
+            let caller = self.env().caller();
+            self.unsafe_subtract_from_balance(caller, token, amount);
+            let transferred = if token == AccountId::from(NATIVE) {
+                self.env().transfer(caller, amount).is_ok()
+            } else {
+                let mut erc20: Erc20 = Erc20::from_account_id(token);
+                erc20.transfer(caller, amount, Vec::new()).is_ok()
+            };
+            if !transferred {
+                self.unsafe_add_to_balance(caller, token, amount);
+                return Err(String::from("token transfer failed"));
+            }
+            self.env().emit_event(Withdraw {
+                member_address: caller,
+                token,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Whether the Moloch keeps balances of `token`, or mints it, so that a call to it
+        /// could move tokens behind the back of the internal ledger.
+        fn _is_accounted_token(&self, token: AccountId) -> bool {
+            self.token_whitelist.contains_key(&token)
+                || self.proposed_to_whitelist.contains_key(&token)
+                || self
+                    .delisted_tokens
+                    .iter()
+                    .any(|delisted| *delisted == token)
+                || self.shares_token == Some(token)
+                || self.loot_token == Some(token)
+        }
+
+        fn _ensure_not_paused(&self) -> Result<(), String> {
+            self._ensure_not_exported()?;
+            ensure!(!self.paused, "contract is paused");
+            Ok(())
+        }
+
+        /// Rebuilds the state exported by another deployment.
+        fn _import_snapshot(&mut self, snapshot: StateSnapshot) {
+            self.summoning_time = snapshot.summoning_time;
+            self.period_duration = snapshot.period_duration;
+            self.voting_period_length = snapshot.parameters.voting_period_length;
+            self.grace_period_length = snapshot.parameters.grace_period_length;
+            self.proposal_deposit = snapshot.parameters.proposal_deposit;
+            self.dilution_bound = snapshot.parameters.dilution_bound;
+            self.processing_reward = snapshot.parameters.processing_reward;
+            self.quorum_percentage = snapshot.quorum_percentage;
+            self.supermajority_percentage = snapshot.supermajority_percentage;
+            self.voting_mode = snapshot.voting_mode;
+            self.guardian = snapshot.guardian;
+            self.guardian_expiry_period = snapshot.guardian_expiry_period;
+            self.paused = snapshot.paused;
+            for (member_address, grants) in snapshot.vesting_grants {
+                self.vesting_grants.insert(member_address, grants);
+            }
+
+            for token in snapshot.approved_tokens {
+                self.token_whitelist.insert(token, true);
+                self.approved_tokens.push(token);
+            }
+            for token in snapshot.delisted_tokens {
+                self.delisted_tokens.push(token);
+            }
+
+            for (member_address, member) in snapshot.members {
+                self.total_shares += member.shares;
+                self.total_loot += member.loot;
+                self.total_voting_weight += self.voting_mode.weight(member.shares);
+                self.total_unvested += member.unvested_shares + member.unvested_loot;
+                self.member_address_by_delegate_key
+                    .insert(member.delegate_key, member_address);
+                self.members.insert(member_address, member);
+            }
+
+            for (key, balance) in snapshot.user_token_balances {
+                self.user_token_balances.insert(key, balance);
+            }
+            self.total_guild_bank_tokens = snapshot.total_guild_bank_tokens;
+
+            self.proposal_count = snapshot.proposal_count;
+            for proposal_id in snapshot.proposal_queue {
+                self.proposal_queue.push(proposal_id);
+            }
+            for ProposalSnapshot {
+                proposal_id,
+                proposal,
+                kind,
+                parameters,
+                vesting,
+            } in snapshot.proposals
+            {
+                // restore the guards of the sponsored proposals not processed yet
+                if proposal.flags[0] && !proposal.flags[1] {
+                    if proposal.flags[4] {
+                        let token = proposal
+                            .tribute_token
+                            .expect("proposal has no tribute token");
+                        self.proposed_to_whitelist.insert(token, true);
+                    } else if proposal.flags[5] {
+                        let applicant = proposal.applicant.expect("proposal has no applicant");
+                        self.proposed_to_kick.insert(applicant, true);
+                    } else if let Some(ProposalKind::Dewhitelist(token)) = kind {
+                        self.proposed_to_dewhitelist.insert(token, true);
+                    }
+                }
+                self.proposals.insert(proposal_id, proposal);
+                if let Some(kind) = kind {
+                    self.proposal_kinds.insert(proposal_id, kind);
+                }
+                if let Some(parameters) = parameters {
+                    self.proposal_parameters.insert(proposal_id, parameters);
+                }
+                if let Some(vesting) = vesting {
+                    self.proposal_vesting.insert(proposal_id, vesting);
+                }
+            }
+            for (key, vote) in snapshot.votes {
+                self.votes_by_member.insert(key, vote);
+            }
+            for (proposal_id, ballots) in snapshot.ballots {
+                self.ballots.insert(proposal_id, ballots);
+            }
+            for (delegator, delegate) in snapshot.vote_delegates {
+                let mut delegators = self
+                    .vote_delegators
+                    .get(&delegate)
+                    .cloned()
+                    .unwrap_or_default();
+                delegators.push(delegator);
+                self.vote_delegators.insert(delegate, delegators);
+                self.vote_delegates.insert(delegator, delegate);
+            }
+            for (key, delegated_vote) in snapshot.delegated_votes {
+                self.delegated_votes.insert(key, delegated_vote);
+            }
+            self.conviction_proposal_count = snapshot.conviction_proposal_count;
+            for (conviction_proposal_id, proposal) in snapshot.conviction_proposals {
+                self.conviction_proposals
+                    .insert(conviction_proposal_id, proposal);
+            }
+            for ((conviction_proposal_id, member_address), stake) in snapshot.conviction_stakes {
+                let staked_shares = self.staked_shares(member_address);
+                self.staked_shares
+                    .insert(member_address, staked_shares + stake);
+                let mut staked_proposals = self
+                    .staked_proposals
+                    .get(&member_address)
+                    .cloned()
+                    .unwrap_or_default();
+                staked_proposals.push(conviction_proposal_id);
+                self.staked_proposals
+                    .insert(member_address, staked_proposals);
+                self.conviction_stakes
+                    .insert((conviction_proposal_id, member_address), stake);
+            }
+        }
+
+        /// Once a proposal approved the export, the state must keep matching the export.
+        fn _ensure_not_exported(&self) -> Result<(), String> {
+            ensure!(self.successor.is_none(), "state has been exported");
+            Ok(())
+        }
+
+        /// The number of storage entries an export would go through, an upper bound of the
+        /// size of the snapshot.
+        fn _export_entries(&self) -> u128 {
+            [
+                self.approved_tokens.len(),
+                self.delisted_tokens.len(),
+                self.members.len(),
+                self.user_token_balances.len(),
+                self.proposals.len(),
+                self.votes_by_member.len(),
+                self.ballots.len(),
+                self.vote_delegates.len(),
+                self.delegated_votes.len(),
+                self.conviction_proposals.len(),
+                self.conviction_stakes.len(),
+                self.vesting_grants.len(),
+            ]
+            .iter()
+            .map(|len| *len as u128)
+            .sum()
+        }
+
+        /// Switches the pause state and starts a new round of pause votes.
+        fn _pause_threshold_reached(&self) -> bool {
+            let weight = self.pause_votes_weight();
+            weight > 0
+                && weight * 100
+                    >= constant::PAUSE_SUPERMAJORITY_PERCENTAGE * self.total_voting_weight
+        }
+
+        fn _set_paused(&mut self, paused: bool, account: AccountId) {
+            self.paused = paused;
+            self.pause_round += 1;
+            if paused {
+                self.env().emit_event(Paused { account });
+            } else {
+                self.env().emit_event(Unpaused { account });
+            }
+        }
+
+        fn _validate_proposal_for_processing(
+            &self,
+            proposal_index: ProposalIndex,
+        ) -> Result<ProposalId, String> {
+            self._ensure_not_paused()?;
+            ensure!(
+                proposal_index < self.proposal_queue.len() as u128,
+                "proposal does not exist"
+            );
+            let proposal_id = self.proposal_queue[proposal_index as u32];
+            let proposal = self.proposals(proposal_id).expect("proposal not found");
+            let parameters = self._proposal_parameters(proposal_id);
+
+            ensure!(
+                self.get_current_period()
+                    >= proposal.starting_period
+                        + parameters.voting_period_length
+                        + parameters.grace_period_length,
+                "proposal is not ready to be processed"
+            );
+            ensure!(!proposal.flags[1], "proposal has already been processed");
+            ensure!(
+                proposal_index == 0
+                    || self
+                        .proposal_queue(proposal_index - 1)
+                        .and_then(|previous_id| self.proposals(previous_id))
+                        .map_or(false, |previous| previous.flags[1]),
+                "previous proposal must be processed"
+            );
+            Ok(proposal_id)
+        }
+
+        fn _did_pass(&self, proposal_id: ProposalId, proposal: &Proposal) -> bool {
+            let mut did_pass = proposal.yes_votes > proposal.no_votes;
+
+            // Make the proposal fail if the dilutionBound is exceeded
+            if (self.total_shares + self.total_loot)
+                * self._proposal_parameters(proposal_id).dilution_bound
+                < proposal.max_total_shares_and_loot_at_yes_vote
+            {
+                did_pass = false;
+            }
+
+            // Make the proposal fail if not enough shares have voted on it
+            if (proposal.yes_votes + proposal.no_votes) * 100
+                < self.quorum_percentage * self.total_voting_weight
+            {
+                did_pass = false;
+            }
+
+            // Make the proposal fail if the share of yes votes is below the supermajority
+            if let Some(supermajority_percentage) = self.supermajority_percentage {
+                if proposal.yes_votes * 100
+                    < supermajority_percentage * (proposal.yes_votes + proposal.no_votes)
+                {
+                    did_pass = false;
+                }
+            }
+
+            // Make the proposal fail if the applicant is jailed
+            // - for standard proposals, we don't want the applicant to get any shares/loot/payment
+            // - for guild kick proposals, we should never be able to propose to kick a jailed member (or have two kick proposals active), so it doesn't matter
+            if let Some(applicant) = proposal.applicant {
+                if self
+                    .members(applicant)
+                    .map_or(false, |member| member.jailed != 0)
+                {
+                    did_pass = false;
+                }
+            }
+
+            did_pass
+        }
+
+        /// Settles the proposal deposit held in ESCROW once the proposal is processed,
+        /// the processor is paid the processing reward and the rest goes back to the sponsor.
+        fn _settle_deposit(&mut self, proposal_id: ProposalId, sponsor: AccountId) {
+            let processor = self.env().caller();
+            let deposit_token = self.deposit_token();
+            let parameters = self._proposal_parameters(proposal_id);
+            let refunded = parameters.proposal_deposit - parameters.processing_reward;
+
+            self.unsafe_internal_transfer(
+                AccountId::from(ESCROW),
+                processor,
+                deposit_token,
+                parameters.processing_reward,
+            );
+            self.unsafe_internal_transfer(
+                AccountId::from(ESCROW),
+                sponsor,
+                deposit_token,
+                refunded,
+            );
+
+            self.env().emit_event(DepositSettled {
+                proposal_id,
+                sponsor,
+                processor,
+                processing_reward: parameters.processing_reward,
+                refunded,
+            });
+        }
+
+        fn _current_parameters(&self) -> Parameters {
+            Parameters {
+                voting_period_length: self.voting_period_length,
+                grace_period_length: self.grace_period_length,
+                proposal_deposit: self.proposal_deposit,
+                dilution_bound: self.dilution_bound,
+                processing_reward: self.processing_reward,
+            }
+        }
+
+        /// Returns the parameters the proposal was sponsored under, the current ones if not sponsored yet.
+        fn _proposal_parameters(&self, proposal_id: ProposalId) -> Parameters {
+            self.proposal_parameters
+                .get(&proposal_id)
+                .copied()
+                .unwrap_or_else(|| self._current_parameters())
+        }
+
+        /// Checks new parameters against the same limits as the constructor.
+        fn _validate_parameters(parameters: &Parameters) -> Result<(), String> {
+            ensure!(
+                parameters.voting_period_length > 0,
+                "_votingPeriodLength cannot be 0"
+            );
+            ensure!(
+                parameters.voting_period_length <= constant::MAX_VOTING_PERIOD_LENGTH,
+                "_votingPeriodLength exceeds limit"
+            );
+            ensure!(
+                parameters.grace_period_length <= constant::MAX_GRACE_PERIOD_LENGTH,
+                "_gracePeriodLength exceeds limit"
+            );
+            ensure!(parameters.dilution_bound > 0, "_dilutionBound cannot be 0");
+            ensure!(
+                parameters.dilution_bound <= constant::MAX_DILUTION_BOUND,
+                "_dilutionBound exceeds limit"
+            );
+            ensure!(
+                parameters.proposal_deposit >= parameters.processing_reward,
+                "_proposalDeposit cannot be smaller than _processingReward"
+            );
+            Ok(())
+        }
     }
 }
@@ -30,6 +30,10 @@ pub struct Member {
     pub highest_index_yes_vote: u128,
     // set to proposalIndex of a passing guild kick proposal for this member, prevents voting on and sponsoring proposals
     pub jailed: ProposalId,
+    /// shares granted with a vesting schedule and not claimed yet, neither voting nor ragequittable
+    pub unvested_shares: u128,
+    /// loot granted with a vesting schedule and not claimed yet, not ragequittable
+    pub unvested_loot: u128,
 }
 
 impl Member {
@@ -41,6 +45,8 @@ impl Member {
             exists: true,
             highest_index_yes_vote: 0,
             jailed: 0,
+            unvested_shares: 0,
+            unvested_loot: 0,
         }
     }
 }
@@ -0,0 +1,267 @@
+//! MULTI-OPTION PROPOSALS
+//! Proposals choosing one of several options, tallied by plurality or instant-runoff.
+
+use ink_env::AccountId;
+use ink_prelude::{vec, vec::Vec};
+
+/// Defines Payment, the payment made from the guild bank when an option wins.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct Payment {
+    /// the account paid
+    pub applicant: AccountId,
+    /// amount of tokens requested as payment
+    pub payment_requested: u128,
+    /// payment token contract reference
+    pub payment_token: AccountId,
+}
+
+/// Defines TallyMethod.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub enum TallyMethod {
+    /// the option ranked first by the most weight wins
+    Plurality,
+    /// the option with the least weight is eliminated until one has a majority
+    InstantRunoff,
+}
+
+/// Defines MultiOption, the payload of a multi-option proposal.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct MultiOption {
+    /// the payment linked to each option, if any
+    pub options: Vec<Option<Payment>>,
+    pub method: TallyMethod,
+}
+
+/// Defines Ballot, the options ranked by a member, most preferred first.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct Ballot {
+    pub member_address: AccountId,
+    pub weight: u128,
+    pub ranking: Vec<u8>,
+}
+
+/// Returns whether the ranking only holds distinct options of a proposal with `option_count` options.
+pub fn is_valid_ranking(ranking: &[u8], option_count: usize) -> bool {
+    !ranking.is_empty()
+        && ranking
+            .iter()
+            .enumerate()
+            .all(|(i, option)| (*option as usize) < option_count && !ranking[..i].contains(option))
+}
+
+/// Tallies the ballots, returning the weight of each option and the winner, if any.
+///
+/// Plurality weighs first preferences, instant-runoff the preferences left in the final
+/// round. Ties leave the proposal without a winner.
+pub fn tally(
+    method: TallyMethod,
+    option_count: usize,
+    ballots: &[Ballot],
+) -> (Vec<u128>, Option<u8>) {
+    match method {
+        TallyMethod::Plurality => {
+            let weights = count(option_count, ballots, &vec![false; option_count]);
+            let winner = leader(&weights);
+            (weights, winner)
+        }
+        TallyMethod::InstantRunoff => instant_runoff(option_count, ballots),
+    }
+}
+
+/// Counts the weight of the highest ranked option not eliminated on each ballot.
+fn count(option_count: usize, ballots: &[Ballot], eliminated: &[bool]) -> Vec<u128> {
+    let mut weights = vec![0; option_count];
+    for ballot in ballots {
+        if let Some(option) = ballot
+            .ranking
+            .iter()
+            .find(|option| !eliminated[**option as usize])
+        {
+            weights[*option as usize] += ballot.weight;
+        }
+    }
+    weights
+}
+
+/// Returns the option with strictly the most weight.
+fn leader(weights: &[u128]) -> Option<u8> {
+    let max = *weights.iter().max()?;
+    let mut leaders = weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight == max);
+    match (leaders.next(), leaders.next()) {
+        (Some((option, _)), None) if max > 0 => Some(option as u8),
+        _ => None,
+    }
+}
+
+fn instant_runoff(option_count: usize, ballots: &[Ballot]) -> (Vec<u128>, Option<u8>) {
+    let mut eliminated = vec![false; option_count];
+    loop {
+        let weights = count(option_count, ballots, &eliminated);
+        let total: u128 = weights.iter().sum();
+        let remaining = eliminated.iter().filter(|eliminated| !**eliminated).count();
+
+        // a majority of the weight still counted wins, as does the last option standing
+        if let Some((option, _)) = weights
+            .iter()
+            .enumerate()
+            .find(|(_, weight)| **weight * 2 > total)
+        {
+            return (weights, Some(option as u8));
+        }
+        if remaining <= 1 || total == 0 {
+            return (weights, None);
+        }
+
+        // eliminate the options with the least weight, all of them when they are tied
+        let min = (0..option_count)
+            .filter(|option| !eliminated[*option])
+            .map(|option| weights[option])
+            .min()
+            .unwrap_or(0);
+        let losers: Vec<usize> = (0..option_count)
+            .filter(|option| !eliminated[*option] && weights[*option] == min)
+            .collect();
+        if losers.len() == remaining {
+            return (weights, None);
+        }
+        for option in losers {
+            eliminated[option] = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ballot(weight: u128, ranking: &[u8]) -> Ballot {
+        Ballot {
+            member_address: AccountId::from([0x01; 32]),
+            weight,
+            ranking: ranking.to_vec(),
+        }
+    }
+
+    #[test]
+    fn ranking_must_hold_distinct_options() {
+        assert!(is_valid_ranking(&[2, 0, 1], 3));
+        assert!(is_valid_ranking(&[1], 3));
+        assert!(!is_valid_ranking(&[], 3));
+        assert!(!is_valid_ranking(&[3], 3));
+        assert!(!is_valid_ranking(&[1, 1], 3));
+    }
+
+    #[test]
+    fn plurality_counts_first_preferences() {
+        let ballots = [ballot(4, &[0, 1]), ballot(3, &[1, 0]), ballot(2, &[2, 1])];
+        assert_eq!(
+            tally(TallyMethod::Plurality, 3, &ballots),
+            (vec![4, 3, 2], Some(0))
+        );
+    }
+
+    #[test]
+    fn plurality_tie_has_no_winner() {
+        let ballots = [ballot(3, &[0]), ballot(3, &[1])];
+        assert_eq!(
+            tally(TallyMethod::Plurality, 2, &ballots),
+            (vec![3, 3], None)
+        );
+        assert_eq!(tally(TallyMethod::Plurality, 2, &[]), (vec![0, 0], None));
+    }
+
+    #[test]
+    fn instant_runoff_transfers_eliminated_preferences() {
+        // option 2 is eliminated first and its weight moves to option 1
+        let ballots = [ballot(4, &[0, 1]), ballot(3, &[1, 0]), ballot(2, &[2, 1])];
+        assert_eq!(
+            tally(TallyMethod::InstantRunoff, 3, &ballots),
+            (vec![4, 5, 0], Some(1))
+        );
+    }
+
+    #[test]
+    fn instant_runoff_majority_wins_first_round() {
+        let ballots = [ballot(6, &[2]), ballot(3, &[1, 2]), ballot(2, &[0])];
+        assert_eq!(
+            tally(TallyMethod::InstantRunoff, 3, &ballots),
+            (vec![2, 3, 6], Some(2))
+        );
+    }
+
+    #[test]
+    fn instant_runoff_exhausted_ballots_are_dropped() {
+        // the ballot for option 2 only has no further preference
+        let ballots = [ballot(4, &[0]), ballot(3, &[1]), ballot(2, &[2])];
+        assert_eq!(
+            tally(TallyMethod::InstantRunoff, 3, &ballots),
+            (vec![4, 3, 0], Some(0))
+        );
+    }
+
+    #[test]
+    fn instant_runoff_full_tie_has_no_winner() {
+        let ballots = [ballot(3, &[0, 1]), ballot(3, &[1, 0])];
+        assert_eq!(
+            tally(TallyMethod::InstantRunoff, 2, &ballots),
+            (vec![3, 3], None)
+        );
+    }
+}
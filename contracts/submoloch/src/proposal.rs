@@ -1,7 +1,12 @@
+use crate::multi_option::MultiOption;
 use ink_env::AccountId;
 use ink_prelude::string::String;
+use ink_prelude::vec::Vec;
 
+/// Defines Vote.
 #[derive(
+    Copy,
+    Clone,
     Debug,
     PartialEq,
     Eq,
@@ -20,6 +25,124 @@ pub enum Vote {
     No,
 }
 
+impl Vote {
+    /// Converts the `uint_vote` used by the RPC calls into a `Vote`.
+    pub fn from_u8(uint_vote: u8) -> Option<Self> {
+        match uint_vote {
+            0 => Some(Vote::None),
+            1 => Some(Vote::Yes),
+            2 => Some(Vote::No),
+            _ => None,
+        }
+    }
+}
+
+/// Defines Action, a call of another contract made by the Moloch.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct Action {
+    /// the contract to call
+    pub target: AccountId,
+    /// the selector of the message to call
+    pub selector: [u8; 4],
+    /// the SCALE-encoded arguments of the message
+    pub input: Vec<u8>,
+    /// the amount of native currency transferred from the guild bank with the call
+    pub value: u128,
+}
+
+/// Defines CallInput, the SCALE-encoded arguments of an `Action` passed on as is.
+pub struct CallInput<'a>(pub &'a [u8]);
+
+impl scale::Encode for CallInput<'_> {
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(self.0);
+    }
+}
+
+/// Defines CallOutput, the raw SCALE-encoded value returned by the target of an `Action`.
+pub struct CallOutput(pub Vec<u8>);
+
+impl scale::Decode for CallOutput {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let mut output = Vec::new();
+        while let Ok(byte) = input.read_byte() {
+            output.push(byte);
+        }
+        Ok(CallOutput(output))
+    }
+}
+
+/// Defines Parameters, the settings a proposal is voted and processed under.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct Parameters {
+    /// number of periods voting is open
+    pub voting_period_length: u128,
+    /// number of periods after voting ends before the proposal can be processed
+    pub grace_period_length: u128,
+    /// deposit paid by the sponsor, in the deposit token
+    pub proposal_deposit: u128,
+    /// maximum multiplier a yes voter will be obligated to pay in case of mass ragequit
+    pub dilution_bound: u128,
+    /// part of the deposit paid to the processor of the proposal
+    pub processing_reward: u128,
+}
+
+/// Defines ProposalKind, the payload of proposals which are neither standard,
+/// whitelist nor guildkick proposals.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub enum ProposalKind {
+    Action(Action),
+    Parameters(Parameters),
+    /// removes the token from the whitelist
+    Dewhitelist(AccountId),
+    /// freezes the Moloch and exports its state to the given successor
+    ExportState(AccountId),
+    /// chooses one of several options
+    MultiOption(MultiOption),
+    /// removes the guardian before its role expires
+    RemoveGuardian,
+}
+
 /// Defines Proposal.
 #[derive(
     Copy,
@@ -115,3 +238,5 @@ pub type ProposalId = u128;
 pub type ProposalIndex = u128;
 pub type ProposalQueue = ink_storage::collections::Vec<ProposalIndex>;
 pub type Proposals = ink_storage::collections::HashMap<ProposalId, Proposal>;
+pub type ProposalKinds = ink_storage::collections::HashMap<ProposalId, ProposalKind>;
+pub type ProposalParameters = ink_storage::collections::HashMap<ProposalId, Parameters>;
@@ -0,0 +1,63 @@
+use crate::conviction::{ConvictionProposal, ConvictionProposalId};
+use crate::member::Member;
+use crate::multi_option::Ballot;
+use crate::proposal::{Parameters, Proposal, ProposalId, ProposalKind, Vote};
+use crate::vesting::{VestingGrant, VestingSchedule};
+use crate::voting::VotingMode;
+use ink_env::AccountId;
+use ink_prelude::vec::Vec;
+
+/// Defines ProposalSnapshot, a proposal along with its payload.
+#[derive(Clone, Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ProposalSnapshot {
+    pub proposal_id: ProposalId,
+    pub proposal: Proposal,
+    /// the payload of proposals which are neither standard, whitelist nor guildkick proposals
+    pub kind: Option<ProposalKind>,
+    /// the parameters the proposal was sponsored under
+    pub parameters: Option<Parameters>,
+    /// the vesting schedule of the shares and loot requested
+    pub vesting: Option<VestingSchedule>,
+}
+
+/// Defines StateSnapshot, the state needed to rebuild a Submoloch in another deployment.
+#[derive(Clone, Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StateSnapshot {
+    /// version of the storage layout the snapshot was exported from
+    pub version: u32,
+    pub summoning_time: u64,
+    pub period_duration: u16,
+    pub parameters: Parameters,
+    pub quorum_percentage: u128,
+    pub supermajority_percentage: Option<u128>,
+    pub voting_mode: VotingMode,
+    pub approved_tokens: Vec<AccountId>,
+    pub delisted_tokens: Vec<AccountId>,
+    pub members: Vec<(AccountId, Member)>,
+    /// non-zero balances of each (user, token)
+    pub user_token_balances: Vec<((AccountId, AccountId), u128)>,
+    pub total_guild_bank_tokens: u128,
+    pub proposal_count: u128,
+    pub proposal_queue: Vec<ProposalId>,
+    /// the queued proposals and the submitted ones waiting for a sponsor
+    pub proposals: Vec<ProposalSnapshot>,
+    /// the votes on proposals not processed yet
+    pub votes: Vec<((ProposalId, AccountId), Vote)>,
+    /// the ballots on multi-option proposals not processed yet
+    pub ballots: Vec<(ProposalId, Vec<Ballot>)>,
+    /// the member each delegator delegated its votes to
+    pub vote_delegates: Vec<(AccountId, AccountId)>,
+    /// the shares counted by a delegate for each delegator on proposals not processed yet
+    pub delegated_votes: Vec<((ProposalId, AccountId), (AccountId, u128))>,
+    pub conviction_proposal_count: u128,
+    pub conviction_proposals: Vec<(ConvictionProposalId, ConvictionProposal)>,
+    /// the shares each member staked on each conviction proposal
+    pub conviction_stakes: Vec<((ConvictionProposalId, AccountId), u128)>,
+    pub guardian: Option<AccountId>,
+    pub guardian_expiry_period: u128,
+    pub paused: bool,
+    /// the shares and loot granted to each member and not fully claimed yet
+    pub vesting_grants: Vec<(AccountId, Vec<VestingGrant>)>,
+}
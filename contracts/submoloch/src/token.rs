@@ -0,0 +1,13 @@
+use ink_prelude::string::String;
+
+/// Defines TokenInfo, the metadata of a whitelisted token.
+#[derive(Clone, Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct TokenInfo {
+    /// the name of the token, if any
+    pub name: Option<String>,
+    /// the symbol of the token, if any
+    pub symbol: Option<String>,
+    /// the number of decimals of the token
+    pub decimals: u8,
+}
@@ -0,0 +1,136 @@
+//! VESTING
+//! Shares and loot granted by a proposal may unlock gradually: nothing before the cliff,
+//! then linearly over the vesting periods counted from the period the proposal was processed.
+
+/// Defines VestingSchedule, in DAO periods.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct VestingSchedule {
+    /// the periods before anything can be claimed
+    pub cliff_periods: u128,
+    /// the periods over which the grant unlocks linearly, proposals require at least one and
+    /// no fewer than `cliff_periods`
+    pub vesting_periods: u128,
+}
+
+/// Defines VestingGrant, shares and loot granted to a member and not fully claimed yet.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub struct VestingGrant {
+    pub shares: u128,
+    pub loot: u128,
+    /// the period in which the granting proposal was processed
+    pub start_period: u128,
+    pub schedule: VestingSchedule,
+    pub claimed_shares: u128,
+    pub claimed_loot: u128,
+}
+
+impl VestingGrant {
+    /// Returns the shares and loot vested in the given period and not claimed yet.
+    pub fn claimable(&self, period: u128) -> (u128, u128) {
+        (
+            vested(self.shares, self.start_period, self.schedule, period) - self.claimed_shares,
+            vested(self.loot, self.start_period, self.schedule, period) - self.claimed_loot,
+        )
+    }
+
+    /// Whether everything granted has been claimed.
+    pub fn is_claimed(&self) -> bool {
+        self.claimed_shares == self.shares && self.claimed_loot == self.loot
+    }
+}
+
+/// Returns how much of `total` granted in `start_period` is vested in `period`.
+pub fn vested(total: u128, start_period: u128, schedule: VestingSchedule, period: u128) -> u128 {
+    let elapsed = period.saturating_sub(start_period);
+    if elapsed < schedule.cliff_periods {
+        0
+    } else if elapsed >= schedule.vesting_periods {
+        total
+    } else {
+        total * elapsed / schedule.vesting_periods
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEDULE: VestingSchedule = VestingSchedule {
+        cliff_periods: 2,
+        vesting_periods: 10,
+    };
+
+    #[test]
+    fn nothing_vests_before_the_cliff() {
+        assert_eq!(vested(100, 5, SCHEDULE, 0), 0);
+        assert_eq!(vested(100, 5, SCHEDULE, 6), 0);
+    }
+
+    #[test]
+    fn vests_linearly_after_the_cliff() {
+        assert_eq!(vested(100, 5, SCHEDULE, 7), 20);
+        assert_eq!(vested(100, 5, SCHEDULE, 10), 50);
+        assert_eq!(vested(100, 5, SCHEDULE, 15), 100);
+        assert_eq!(vested(100, 5, SCHEDULE, 1000), 100);
+    }
+
+    #[test]
+    fn everything_vests_at_the_cliff_without_vesting_periods() {
+        let schedule = VestingSchedule {
+            cliff_periods: 3,
+            vesting_periods: 0,
+        };
+        assert_eq!(vested(100, 0, schedule, 2), 0);
+        assert_eq!(vested(100, 0, schedule, 3), 100);
+    }
+
+    #[test]
+    fn claimable_excludes_claimed_amounts() {
+        let mut grant = VestingGrant {
+            shares: 100,
+            loot: 10,
+            start_period: 0,
+            schedule: SCHEDULE,
+            claimed_shares: 0,
+            claimed_loot: 0,
+        };
+        assert_eq!(grant.claimable(5), (50, 5));
+        grant.claimed_shares = 50;
+        grant.claimed_loot = 5;
+        assert_eq!(grant.claimable(5), (0, 0));
+        assert_eq!(grant.claimable(10), (50, 5));
+        assert!(!grant.is_claimed());
+        grant.claimed_shares = 100;
+        grant.claimed_loot = 10;
+        assert!(grant.is_claimed());
+    }
+}
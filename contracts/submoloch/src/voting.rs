@@ -0,0 +1,112 @@
+//! VOTING STRATEGIES
+//! How the shares of a member weigh in the votes on a proposal.
+
+/// Defines VotingMode.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    ink_storage::traits::SpreadLayout,
+    ink_storage::traits::PackedLayout,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+)]
+pub enum VotingMode {
+    /// one vote per share
+    Linear,
+    /// the integer square root of the shares, so large members weigh less
+    Quadratic,
+}
+
+impl Default for VotingMode {
+    fn default() -> Self {
+        VotingMode::Linear
+    }
+}
+
+impl VotingMode {
+    /// Returns the weight of a vote cast with the given shares.
+    pub fn weight(self, shares: u128) -> u128 {
+        match self {
+            VotingMode::Linear => shares,
+            VotingMode::Quadratic => isqrt(shares),
+        }
+    }
+}
+
+/// Returns the integer square root of `n`, rounded down.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method, starting above the root so that the estimates decrease
+    let mut x = n / 2 + 1;
+    let mut y = (x + n / x) / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tallies (yes, no) weights of votes cast with the given shares.
+    fn tally(mode: VotingMode, votes: &[(u128, bool)]) -> (u128, u128) {
+        votes
+            .iter()
+            .fold((0, 0), |(yes, no), (shares, approve)| match approve {
+                true => (yes + mode.weight(*shares), no),
+                false => (yes, no + mode.weight(*shares)),
+            })
+    }
+
+    #[test]
+    fn isqrt_rounds_down() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(2), 1);
+        assert_eq!(isqrt(3), 1);
+        assert_eq!(isqrt(4), 2);
+        assert_eq!(isqrt(99), 9);
+        assert_eq!(isqrt(100), 10);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn linear_weight_is_shares() {
+        assert_eq!(VotingMode::Linear.weight(0), 0);
+        assert_eq!(VotingMode::Linear.weight(100), 100);
+    }
+
+    #[test]
+    fn quadratic_weight_is_square_root_of_shares() {
+        assert_eq!(VotingMode::Quadratic.weight(100), 10);
+        assert_eq!(VotingMode::Quadratic.weight(120), 10);
+    }
+
+    #[test]
+    fn quadratic_matches_linear_with_one_share_each() {
+        let votes = [(1, true), (1, false), (1, true), (1, true), (1, false)];
+        assert_eq!(
+            tally(VotingMode::Quadratic, &votes),
+            tally(VotingMode::Linear, &votes)
+        );
+        assert_eq!(tally(VotingMode::Linear, &votes), (3, 2));
+    }
+
+    #[test]
+    fn quadratic_dampens_large_members() {
+        let votes = [(100, true), (1, false), (1, false), (1, false)];
+        assert_eq!(tally(VotingMode::Linear, &votes), (100, 3));
+        assert_eq!(tally(VotingMode::Quadratic, &votes), (10, 3));
+    }
+}
@@ -1,6 +1,7 @@
 import BN from 'bn.js';
 import * as chai from 'chai';
 import { expect } from 'chai';
+import { blake2AsHex } from '@polkadot/util-crypto';
 const { assert } = chai;
 
 import { patract, network } from 'redspot';
@@ -39,6 +40,8 @@ const revertMessages = {
   molochConstructorTooManyTokens: 'too many tokens',
   molochConstructorDepositCannotBeSmallerThanProcessingReward: '_proposalDeposit cannot be smaller than _processingReward',
   molochConstructorApprovedTokenCannotBe0: '_approvedToken cannot be 0',
+  molochConstructorQuorumPercentageExceedsLimit: '_quorumPercentage exceeds limit',
+  molochConstructorInvalidSupermajorityPercentage: '_supermajorityPercentage must be greater than 50 and not exceed 100',
   molochConstructorDuplicateApprovedToken: 'revert duplicate approved token',
   submitProposalTooManySharesRequested: 'too many shares requested',
   submitProposalProposalMustHaveBeenProposed: 'proposal must have been proposed',
@@ -95,6 +98,9 @@ const zeroAddress = '0x0000000000000000000000000000000000000000'
 const GUILD = '13C2q9xLsW4xTeQCcp3fr44vBEcVQx7sERzKb9iENWDk5FZM'
 const ESCROW = '12KzhL2G5oWLyeFciHKowDgedsLtqXhTzR2Njx4Ksb5DWjkA';
 const TOTAL = '14KRrGnAj13EZkWu72PtGbupXcTF37eyZUJJ4TS1sEr1J3Dh';
+const NATIVE = '0x' + 'ee'.repeat(32);
+// transfer data crediting the guild bank, a SCALE-encoded `None` proposal id
+const TO_GUILD_BANK = [0];
 const MAX_TOKEN_WHITELIST_COUNT = new BN('100') // TODO: actual number to be determined
 
 const _1 = new BN('1')
@@ -109,9 +115,25 @@ const deploymentConfig = {
   'PROPOSAL_DEPOSIT': 10,
   'DILUTION_BOUND': 3,
   'PROCESSING_REWARD': 1,
+  'QUORUM_PERCENTAGE': 50,
+  'SUPERMAJORITY_PERCENTAGE': null,
   'TOKEN_SUPPLY': 10000
 }
 
+// short periods, so that proposals can be voted and processed within a test.
+const processingConfig = {
+  ...deploymentConfig,
+  'PERIOD_DURATION_IN_SECONDS': 2,
+  'VOTING_DURATON_IN_PERIODS': 3,
+  'GRACE_DURATON_IN_PERIODS': 1
+}
+
+const sleep = (ms) => new Promise(resolve => setTimeout(resolve, ms));
+
+async function moveForwardPeriods(periods, config = processingConfig) {
+  await sleep((periods * config.PERIOD_DURATION_IN_SECONDS + 1) * 1000);
+}
+
 async function addressArray(length) {
   // returns an array of distinct non-zero addresses
   let array: string[] = []
@@ -122,7 +144,7 @@ async function addressArray(length) {
   return array
 }
 
-async function setup() {
+async function setup(config = deploymentConfig) {
   const one = new BN(10).pow(new BN(api.registry.chainDecimals[0]));
   const signers = await getSigners();
   const creator = signers[0];
@@ -130,17 +152,19 @@ async function setup() {
   const applicant1 = await getRandomSigner(creator, one.muln(100));
   const applicant2 = await getRandomSigner(creator, one.muln(100));
   const TokenContractFactory = await getContractFactory('erc20', creator);
-  const tokenAlpha = await TokenContractFactory.deploy('new', deploymentConfig.TOKEN_SUPPLY);
+  const tokenAlpha = await TokenContractFactory.deploy('new', config.TOKEN_SUPPLY);
   const SubMolochContractFactory = await getContractFactory('submoloch', creator);
   const submoloch = await SubMolochContractFactory.deploy('new',
     summoner.address,
     [tokenAlpha.address],
-    deploymentConfig.PERIOD_DURATION_IN_SECONDS,
-    deploymentConfig.VOTING_DURATON_IN_PERIODS,
-    deploymentConfig.GRACE_DURATON_IN_PERIODS,
-    deploymentConfig.PROPOSAL_DEPOSIT,
-    deploymentConfig.DILUTION_BOUND,
-    deploymentConfig.PROCESSING_REWARD
+    config.PERIOD_DURATION_IN_SECONDS,
+    config.VOTING_DURATON_IN_PERIODS,
+    config.GRACE_DURATON_IN_PERIODS,
+    config.PROPOSAL_DEPOSIT,
+    config.DILUTION_BOUND,
+    config.PROCESSING_REWARD,
+    config.QUORUM_PERCENTAGE,
+    config.SUPERMAJORITY_PERCENTAGE
   );
   return { creator, summoner, applicant1, applicant2, tokenAlpha, submoloch, SubMolochContractFactory, TokenContractFactory };
 }
@@ -189,11 +213,13 @@ describe('Submoloch', () => {
       'exists': 'bool',
       'highestIndexYesVote': 'u128',
       'jailed': 'ProposalId',
+      'unvestedShares': 'u128',
+      'unvestedLoot': 'u128',
     }
   });
 
   const fundAndApproveToMoloch = async (token, moloch, { to, from, value }) => {
-    await token.transfer(to, value, { from: from });
+    await token.transfer(to, value, [], { from: from });
     await token.approve(moloch.address, value, { from: to });
   }
 
@@ -208,7 +234,7 @@ describe('Submoloch', () => {
       const depositToken = tokenAlpha;
 
       // transfer initial funds.
-      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, { from: creator.address });
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { from: creator.address });
 
       // const now = await api.query.timestamp.now();
 
@@ -236,6 +262,12 @@ describe('Submoloch', () => {
       const processingReward = await submoloch.processingReward()
       assert.equal(processingReward.output, deploymentConfig.PROCESSING_REWARD)
 
+      const quorumPercentage = await submoloch.quorumPercentage();
+      assert.equal(quorumPercentage.output, deploymentConfig.QUORUM_PERCENTAGE)
+
+      const supermajorityPercentage = await submoloch.supermajorityPercentage();
+      assert.isTrue(supermajorityPercentage.output.isNone);
+
       const currentPeriod = await submoloch.getCurrentPeriod();
       assert.equal(currentPeriod.output, 0);
 
@@ -282,6 +314,37 @@ describe('Submoloch', () => {
       assert.deepEqual(firstWhitelistedToken.output, tokenAlpha.address);
     });
 
+    it('token info of approved tokens', async () => {
+      const { summoner, SubMolochContractFactory, TokenContractFactory } = await setup();
+      const tokenBeta = await TokenContractFactory.deploy('newWithMetadata',
+        deploymentConfig.TOKEN_SUPPLY,
+        'Beta Token',
+        'BETA',
+        18
+      );
+      const molochTemp = await SubMolochContractFactory.deploy('new',
+        summoner.address,
+        [tokenBeta.address],
+        deploymentConfig.PERIOD_DURATION_IN_SECONDS,
+        deploymentConfig.VOTING_DURATON_IN_PERIODS,
+        deploymentConfig.GRACE_DURATON_IN_PERIODS,
+        deploymentConfig.PROPOSAL_DEPOSIT,
+        deploymentConfig.DILUTION_BOUND,
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
+      );
+
+      const tokenInfo = (await molochTemp.tokenInfo(tokenBeta.address)).output.unwrap();
+      assert.equal(tokenInfo.name.unwrap().toString(), 'Beta Token');
+      assert.equal(tokenInfo.symbol.unwrap().toString(), 'BETA');
+      assert.equal(+tokenInfo.decimals, 18);
+
+      // tokens which are not whitelisted have no info.
+      const escrowInfo = (await molochTemp.tokenInfo(ESCROW)).output;
+      assert.isTrue(escrowInfo.isNone);
+    })
+
     it('require fail - summoner can not be zero address', async () => {
       const { SubMolochContractFactory, tokenAlpha } = await setup();
       await SubMolochContractFactory.deploy('new',
@@ -292,7 +355,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
     })
@@ -307,7 +372,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
     })
@@ -322,7 +389,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
     })
@@ -337,7 +406,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
 
@@ -350,7 +421,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
       )
 
       const totalShares = await molochTemp.totalShares();
@@ -367,7 +440,9 @@ describe('Submoloch', () => {
         _1e18Plus1,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @FIXME: ).should.be.rejectedWith(revertMessages.molochConstructorGracePeriodLengthExceedsLimit)
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
@@ -381,7 +456,9 @@ describe('Submoloch', () => {
         _1e18,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
       )
 
       const totalShares = await molochTemp.totalShares();
@@ -398,7 +475,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         0,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @FIXME: ).should.be.rejectedWith(revertMessages.molochConstructorDilutionBoundCannotBe0)
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
@@ -414,7 +493,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         _1e18Plus1,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @FIXME: ).should.be.rejectedWith(revertMessages.molochConstructorDilutionBoundExceedsLimitExceedsLimit)
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
@@ -428,7 +509,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         _1e18,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
       );
 
       const totalShares = await molochTemp.totalShares();
@@ -445,7 +528,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @FIXME: ).should.be.rejectedWith(revertMessages.molochConstructorNeedAtLeastOneApprovedToken)
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
@@ -461,7 +546,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @FIXME: ).should.be.rejectedWith(revertMessages.molochConstructorTooManyTokens)
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
@@ -477,12 +564,98 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         _1e18,
         deploymentConfig.DILUTION_BOUND,
-        _1e18Plus1
+        _1e18Plus1,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @FIXME: ).should.be.rejectedWith(revertMessages.molochConstructorDepositCannotBeSmallerThanProcessingReward)
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
     })
 
+    it('require fail - quorum percentage exceeds limit', async () => {
+      const { summoner, SubMolochContractFactory, tokenAlpha } = await setup();
+      await SubMolochContractFactory.deploy('new',
+        summoner.address,
+        [tokenAlpha.address],
+        deploymentConfig.PERIOD_DURATION_IN_SECONDS,
+        deploymentConfig.VOTING_DURATON_IN_PERIODS,
+        deploymentConfig.GRACE_DURATON_IN_PERIODS,
+        deploymentConfig.PROPOSAL_DEPOSIT,
+        deploymentConfig.DILUTION_BOUND,
+        deploymentConfig.PROCESSING_REWARD,
+        101,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
+        // @FIXME: ).should.be.rejectedWith(revertMessages.molochConstructorQuorumPercentageExceedsLimit)
+        // @ts-ignore
+      ).should.be.rejectedWith('Instantiation failed');
+
+      // still works with 100
+      const molochTemp = await SubMolochContractFactory.deploy('new',
+        summoner.address,
+        [tokenAlpha.address],
+        deploymentConfig.PERIOD_DURATION_IN_SECONDS,
+        deploymentConfig.VOTING_DURATON_IN_PERIODS,
+        deploymentConfig.GRACE_DURATON_IN_PERIODS,
+        deploymentConfig.PROPOSAL_DEPOSIT,
+        deploymentConfig.DILUTION_BOUND,
+        deploymentConfig.PROCESSING_REWARD,
+        100,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
+      );
+
+      const quorumPercentage = await molochTemp.quorumPercentage();
+      assert.equal(+quorumPercentage.output, 100);
+    })
+
+    it('require fail - supermajority percentage must be a majority', async () => {
+      const { summoner, SubMolochContractFactory, tokenAlpha } = await setup();
+      await SubMolochContractFactory.deploy('new',
+        summoner.address,
+        [tokenAlpha.address],
+        deploymentConfig.PERIOD_DURATION_IN_SECONDS,
+        deploymentConfig.VOTING_DURATON_IN_PERIODS,
+        deploymentConfig.GRACE_DURATON_IN_PERIODS,
+        deploymentConfig.PROPOSAL_DEPOSIT,
+        deploymentConfig.DILUTION_BOUND,
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        50
+        // @FIXME: ).should.be.rejectedWith(revertMessages.molochConstructorInvalidSupermajorityPercentage)
+        // @ts-ignore
+      ).should.be.rejectedWith('Instantiation failed');
+
+      await SubMolochContractFactory.deploy('new',
+        summoner.address,
+        [tokenAlpha.address],
+        deploymentConfig.PERIOD_DURATION_IN_SECONDS,
+        deploymentConfig.VOTING_DURATON_IN_PERIODS,
+        deploymentConfig.GRACE_DURATON_IN_PERIODS,
+        deploymentConfig.PROPOSAL_DEPOSIT,
+        deploymentConfig.DILUTION_BOUND,
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        101
+        // @ts-ignore
+      ).should.be.rejectedWith('Instantiation failed');
+
+      // still works with 51
+      const molochTemp = await SubMolochContractFactory.deploy('new',
+        summoner.address,
+        [tokenAlpha.address],
+        deploymentConfig.PERIOD_DURATION_IN_SECONDS,
+        deploymentConfig.VOTING_DURATON_IN_PERIODS,
+        deploymentConfig.GRACE_DURATON_IN_PERIODS,
+        deploymentConfig.PROPOSAL_DEPOSIT,
+        deploymentConfig.DILUTION_BOUND,
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        51
+      );
+
+      const supermajorityPercentage = await molochTemp.supermajorityPercentage();
+      assert.equal(+supermajorityPercentage.output.unwrap(), 51);
+    })
+
     // we may not need this check.
     it('require fail - approved token cannot be zero', async () => {
       const { summoner, SubMolochContractFactory, tokenAlpha } = await setup();
@@ -494,7 +667,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @FIXME: ).should.be.rejectedWith(revertMessages.molochConstructorApprovedTokenCannotBe0)
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
@@ -510,7 +685,9 @@ describe('Submoloch', () => {
         deploymentConfig.GRACE_DURATON_IN_PERIODS,
         deploymentConfig.PROPOSAL_DEPOSIT,
         deploymentConfig.DILUTION_BOUND,
-        deploymentConfig.PROCESSING_REWARD
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE
         // @FIXME: ).should.be.rejectedWith(revertMessages.molochConstructorDuplicateApprovedToken)
         // @ts-ignore
       ).should.be.rejectedWith('Instantiation failed');
@@ -637,6 +814,33 @@ describe('Submoloch', () => {
       )).not.emit(moloch, "SubmitProposal");
     })
 
+    it('require fail - tribute token is paused', async () => {
+      await tokenAlpha.approve(moloch.address, proposal1.tributeOffered, {
+        signer: proposal1.applicant
+      });
+
+      // the token issuer (its creator) freezes the token.
+      await tokenAlpha.pause();
+
+      await expect(moloch.submitProposal(
+        proposal1.applicant.address,
+        proposal1.sharesRequested,
+        proposal1.lootRequested,
+        proposal1.tributeOffered,
+        proposal1.tributeToken.address,
+        proposal1.paymentRequested,
+        proposal1.paymentToken.address,
+        proposal1.details,
+        { signer: proposal1.applicant }
+      )).to.not.emit(moloch, "SubmitProposal");
+
+      await verifyBalance({
+        token: tokenAlpha,
+        address: proposal1.applicant.address,
+        expectedBalance: proposal1.tributeOffered
+      });
+    });
+
     it('require fail - tribute token is not whitelisted', async () => {
       await tokenAlpha.approve(moloch.address, proposal1.tributeOffered, {
         signer: proposal1.applicant
@@ -999,4 +1203,1630 @@ describe('Submoloch', () => {
     })
   });
 
+  describe('processProposal - deposit settlement', () => {
+    let tokenAlpha, moloch;
+    let creator, summoner, processor;
+    let proposal1;
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      moloch = prepared.submoloch;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      processor = prepared.applicant2;
+
+      proposal1 = {
+        applicant: prepared.applicant1,
+        sharesRequested: standardShareRequest,
+        lootRequested: standardLootRequest,
+        tributeOffered: standardTribute,
+        tributeToken: tokenAlpha,
+        paymentRequested: 0,
+        paymentToken: tokenAlpha,
+        details: 'all hail moloch'
+      };
+
+      // the applicant pays the tribute.
+      await tokenAlpha.tx['transfer'](proposal1.applicant.address, proposal1.tributeOffered, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, proposal1.tributeOffered, { signer: proposal1.applicant });
+
+      // the summoner pays the proposal deposit.
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+
+      await moloch.submitProposal(
+        proposal1.applicant.address,
+        proposal1.sharesRequested,
+        proposal1.lootRequested,
+        proposal1.tributeOffered,
+        proposal1.tributeToken.address,
+        proposal1.paymentRequested,
+        proposal1.paymentToken.address,
+        proposal1.details,
+        { signer: proposal1.applicant }
+      );
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+
+      await verifyInternalBalance({
+        moloch: moloch,
+        token: tokenAlpha,
+        user: ESCROW,
+        expectedBalance: proposal1.tributeOffered + processingConfig.PROPOSAL_DEPOSIT
+      });
+    });
+
+    it('happy case - passing proposal pays the processor and refunds the sponsor', async () => {
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await expect(moloch.processProposal(firstProposalIndex, { signer: processor }))
+        .to.emit(moloch, 'DepositSettled');
+
+      await verifyFlags({
+        moloch: moloch,
+        proposalId: firstProposalIndex,
+        expectedFlags: [true, true, true, false, false, false]
+      });
+
+      await verifyInternalBalances({
+        moloch: moloch,
+        token: tokenAlpha,
+        userBalances: {
+          [ESCROW]: 0,
+          [GUILD]: proposal1.tributeOffered,
+          [summoner.address]: processingConfig.PROPOSAL_DEPOSIT - processingConfig.PROCESSING_REWARD,
+          [processor.address]: processingConfig.PROCESSING_REWARD
+        }
+      });
+    });
+
+    it('happy case - failing proposal returns the tribute and refunds the sponsor', async () => {
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, no, { signer: summoner });
+
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await expect(moloch.processProposal(firstProposalIndex, { signer: processor }))
+        .to.emit(moloch, 'DepositSettled');
+
+      await verifyFlags({
+        moloch: moloch,
+        proposalId: firstProposalIndex,
+        expectedFlags: [true, true, false, false, false, false]
+      });
+
+      await verifyInternalBalances({
+        moloch: moloch,
+        token: tokenAlpha,
+        userBalances: {
+          [ESCROW]: 0,
+          [GUILD]: 0,
+          [proposal1.applicant.address]: proposal1.tributeOffered,
+          [summoner.address]: processingConfig.PROPOSAL_DEPOSIT - processingConfig.PROCESSING_REWARD,
+          [processor.address]: processingConfig.PROCESSING_REWARD
+        }
+      });
+    });
+
+    it('happy case - sponsor withdraws the refunded deposit', async () => {
+      await moveForwardPeriods(1 + processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processProposal(firstProposalIndex, { signer: processor });
+
+      const refund = processingConfig.PROPOSAL_DEPOSIT - processingConfig.PROCESSING_REWARD;
+      await expect(moloch.withdrawBalance(tokenAlpha.address, refund, { signer: summoner }))
+        .to.emit(moloch, 'Withdraw');
+
+      await verifyInternalBalance({
+        moloch: moloch,
+        token: tokenAlpha,
+        user: summoner.address,
+        expectedBalance: 0
+      });
+      await verifyBalance({
+        token: tokenAlpha,
+        address: summoner.address,
+        expectedBalance: initSummonerBalance - processingConfig.PROPOSAL_DEPOSIT + refund
+      });
+
+      // nothing is left to withdraw.
+      await expect(moloch.withdrawBalance(tokenAlpha.address, 1, { signer: summoner }))
+        .to.not.emit(moloch, 'Withdraw');
+    });
+
+    it('require fail - proposal is not ready to be processed', async () => {
+      await expect(moloch.processProposal(firstProposalIndex, { signer: processor }))
+        .to.not.emit(moloch, 'DepositSettled');
+
+      await verifyInternalBalance({
+        moloch: moloch,
+        token: tokenAlpha,
+        user: ESCROW,
+        expectedBalance: proposal1.tributeOffered + processingConfig.PROPOSAL_DEPOSIT
+      });
+    });
+  });
+
+  describe('onTokensReceived', () => {
+    let tokenAlpha, moloch;
+    let creator, applicant;
+    let TokenContractFactory;
+
+    beforeEach(async () => {
+      const prepared = await setup();
+      tokenAlpha = prepared.tokenAlpha;
+      moloch = prepared.submoloch;
+      creator = prepared.creator;
+      applicant = prepared.applicant1;
+      TokenContractFactory = prepared.TokenContractFactory;
+
+      await tokenAlpha.tx['transfer'](applicant.address, standardTribute, [], { signer: creator });
+    });
+
+    it('happy case - plain transfer is credited to the guild bank', async () => {
+      await expect(tokenAlpha.tx['transfer'](moloch.address, standardTribute, TO_GUILD_BANK, { signer: applicant }))
+        .to.emit(moloch, 'TokensReceived');
+
+      await verifyBalance({
+        token: tokenAlpha,
+        address: moloch.address,
+        expectedBalance: standardTribute
+      });
+      await verifyInternalBalances({
+        moloch: moloch,
+        token: tokenAlpha,
+        userBalances: {
+          [GUILD]: standardTribute,
+          [TOTAL]: standardTribute
+        }
+      });
+      const totalGuildBankTokens = await moloch.totalGuildBankTokens();
+      assert.equal(+totalGuildBankTokens.output, 1);
+    });
+
+    it('happy case - transfer without data is not notified', async () => {
+      await expect(tokenAlpha.tx['transfer'](moloch.address, standardTribute, [], { signer: applicant }))
+        .to.not.emit(moloch, 'TokensReceived');
+
+      await verifyBalance({
+        token: tokenAlpha,
+        address: moloch.address,
+        expectedBalance: standardTribute
+      });
+      await verifyInternalBalance({
+        moloch: moloch,
+        token: tokenAlpha,
+        user: GUILD,
+        expectedBalance: 0
+      });
+    });
+
+    it('happy case - transfer with a proposal id adds to its tribute', async () => {
+      await tokenAlpha.approve(moloch.address, standardTribute / 2, { signer: applicant });
+      await moloch.submitProposal(
+        applicant.address,
+        standardShareRequest,
+        standardLootRequest,
+        standardTribute / 2,
+        tokenAlpha.address,
+        0,
+        tokenAlpha.address,
+        'all hail moloch',
+        { signer: applicant }
+      );
+
+      const data = api.createType('Option<ProposalId>', firstProposalIndex).toU8a();
+      await expect(tokenAlpha.tx['transfer'](moloch.address, standardTribute / 2, data, { signer: applicant }))
+        .to.emit(moloch, 'TokensReceived');
+
+      const proposalData = (await moloch.proposals(firstProposalIndex)).output.unwrap();
+      assert.equal(+proposalData.tributeOffered.unwrap(), standardTribute);
+      await verifyInternalBalances({
+        moloch: moloch,
+        token: tokenAlpha,
+        userBalances: {
+          [ESCROW]: standardTribute,
+          [GUILD]: 0
+        }
+      });
+    });
+
+    it('require fail - only the proposer can add tribute', async () => {
+      await tokenAlpha.approve(moloch.address, standardTribute / 2, { signer: applicant });
+      await moloch.submitProposal(
+        applicant.address,
+        standardShareRequest,
+        standardLootRequest,
+        standardTribute / 2,
+        tokenAlpha.address,
+        0,
+        tokenAlpha.address,
+        'all hail moloch',
+        { signer: applicant }
+      );
+
+      const data = api.createType('Option<ProposalId>', firstProposalIndex).toU8a();
+      await expect(tokenAlpha.tx['transfer'](moloch.address, standardTribute, data, { signer: creator }))
+        .to.not.emit(tokenAlpha, 'Transfer');
+
+      await verifyBalance({
+        token: tokenAlpha,
+        address: moloch.address,
+        expectedBalance: standardTribute / 2
+      });
+    });
+
+    it('require fail - token is not whitelisted', async () => {
+      const tokenBeta = await TokenContractFactory.deploy('new', deploymentConfig.TOKEN_SUPPLY);
+
+      await expect(tokenBeta.tx['transfer'](moloch.address, standardTribute, TO_GUILD_BANK, { signer: creator }))
+        .to.not.emit(tokenBeta, 'Transfer');
+
+      await verifyBalance({
+        token: tokenBeta,
+        address: moloch.address,
+        expectedBalance: 0
+      });
+    });
+  });
+
+  describe('native currency tribute', () => {
+    let tokenAlpha, moloch;
+    let creator, summoner, applicant;
+    const nativeToken = { address: NATIVE };
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      moloch = await prepared.SubMolochContractFactory.deploy('new',
+        summoner.address,
+        [tokenAlpha.address, NATIVE],
+        processingConfig.PERIOD_DURATION_IN_SECONDS,
+        processingConfig.VOTING_DURATON_IN_PERIODS,
+        processingConfig.GRACE_DURATON_IN_PERIODS,
+        processingConfig.PROPOSAL_DEPOSIT,
+        processingConfig.DILUTION_BOUND,
+        processingConfig.PROCESSING_REWARD,
+        processingConfig.QUORUM_PERCENTAGE,
+        processingConfig.SUPERMAJORITY_PERCENTAGE
+      );
+    });
+
+    it('native token is whitelisted', async () => {
+      const nativeTokenAddress = (await moloch.nativeToken()).output;
+      assert.equal(nativeTokenAddress.toHex(), NATIVE);
+      assert.equal((await moloch.tokenWhitelist(NATIVE)).output, true);
+      assert.isTrue((await moloch.tokenInfo(NATIVE)).output.isNone);
+    });
+
+    it('happy case - transferred value is held in escrow', async () => {
+      await expect(moloch.submitProposal(
+        applicant.address,
+        standardShareRequest,
+        standardLootRequest,
+        standardTribute,
+        NATIVE,
+        0,
+        tokenAlpha.address,
+        'all hail moloch',
+        { signer: applicant, value: standardTribute }
+      )).to.emit(moloch, 'SubmitProposal');
+
+      await verifyInternalBalances({
+        moloch: moloch,
+        token: nativeToken,
+        userBalances: {
+          [ESCROW]: standardTribute,
+          [TOTAL]: standardTribute
+        }
+      });
+    });
+
+    it('require fail - transferred value must equal the tribute', async () => {
+      await expect(moloch.submitProposal(
+        applicant.address,
+        standardShareRequest,
+        standardLootRequest,
+        standardTribute,
+        NATIVE,
+        0,
+        tokenAlpha.address,
+        'all hail moloch',
+        { signer: applicant, value: standardTribute - 1 }
+      )).to.not.emit(moloch, 'SubmitProposal');
+
+      await verifyInternalBalance({
+        moloch: moloch,
+        token: nativeToken,
+        user: ESCROW,
+        expectedBalance: 0
+      });
+    });
+
+    it('happy case - returned tribute is withdrawn in the native currency', async () => {
+      await moloch.submitProposal(
+        applicant.address,
+        standardShareRequest,
+        standardLootRequest,
+        standardTribute,
+        NATIVE,
+        0,
+        tokenAlpha.address,
+        'all hail moloch',
+        { signer: applicant, value: standardTribute }
+      );
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, no, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processProposal(firstProposalIndex, { signer: summoner });
+
+      await verifyInternalBalance({
+        moloch: moloch,
+        token: nativeToken,
+        user: applicant.address,
+        expectedBalance: standardTribute
+      });
+
+      await expect(moloch.withdrawBalances([NATIVE], [standardTribute], { signer: applicant }))
+        .to.emit(moloch, 'Withdraw');
+
+      await verifyInternalBalances({
+        moloch: moloch,
+        token: nativeToken,
+        userBalances: {
+          [applicant.address]: 0,
+          [ESCROW]: 0,
+          [TOTAL]: 0
+        }
+      });
+    });
+  });
+
+  describe('companion tokens', () => {
+    let tokenAlpha, moloch, sharesToken, lootToken;
+    let summoner, applicant;
+
+    beforeEach(async () => {
+      const prepared = await setup();
+      tokenAlpha = prepared.tokenAlpha;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      const one = new BN(10).pow(new BN(api.registry.chainDecimals[0]));
+      moloch = await prepared.SubMolochContractFactory.deploy('newWithOptions',
+        summoner.address,
+        [tokenAlpha.address],
+        deploymentConfig.PERIOD_DURATION_IN_SECONDS,
+        deploymentConfig.VOTING_DURATON_IN_PERIODS,
+        deploymentConfig.GRACE_DURATON_IN_PERIODS,
+        deploymentConfig.PROPOSAL_DEPOSIT,
+        deploymentConfig.DILUTION_BOUND,
+        deploymentConfig.PROCESSING_REWARD,
+        deploymentConfig.QUORUM_PERCENTAGE,
+        deploymentConfig.SUPERMAJORITY_PERCENTAGE,
+        'Linear',
+        null,
+        [prepared.TokenContractFactory.abi.project.source.wasmHash, true],
+        { value: one.muln(10) }
+      );
+      sharesToken = prepared.TokenContractFactory.attach((await moloch.sharesToken()).output.unwrap().toString());
+      lootToken = prepared.TokenContractFactory.attach((await moloch.lootToken()).output.unwrap().toString());
+    });
+
+    it('summoner share is mirrored by the shares token', async () => {
+      await verifyBalance({ token: sharesToken, address: summoner.address, expectedBalance: summonerShares });
+      await verifyBalance({ token: lootToken, address: summoner.address, expectedBalance: 0 });
+      assert.equal((await sharesToken.transferable()).output, false);
+      assert.equal((await lootToken.transferable()).output, true);
+      expect((await lootToken.controller()).output.unwrap()).to.eq(moloch.address);
+    });
+
+    it('require fail - shares are not transferable', async () => {
+      await expect(sharesToken.tx['transfer'](applicant.address, 1, [], { signer: summoner }))
+        .to.not.emit(sharesToken, 'Transfer');
+
+      await verifyBalance({ token: sharesToken, address: summoner.address, expectedBalance: summonerShares });
+    });
+
+    it('require fail - summoner has no loot to transfer', async () => {
+      await expect(lootToken.tx['transfer'](applicant.address, 1, [], { signer: summoner }))
+        .to.not.emit(lootToken, 'Transfer');
+    });
+  });
+
+  describe('companion tokens - loot transfers', () => {
+    let tokenAlpha, moloch, lootToken;
+    let creator, summoner, applicant;
+    const lootGranted = 10;
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      const one = new BN(10).pow(new BN(api.registry.chainDecimals[0]));
+      moloch = await prepared.SubMolochContractFactory.deploy('newWithOptions',
+        summoner.address,
+        [tokenAlpha.address],
+        processingConfig.PERIOD_DURATION_IN_SECONDS,
+        processingConfig.VOTING_DURATON_IN_PERIODS,
+        processingConfig.GRACE_DURATON_IN_PERIODS,
+        processingConfig.PROPOSAL_DEPOSIT,
+        processingConfig.DILUTION_BOUND,
+        processingConfig.PROCESSING_REWARD,
+        processingConfig.QUORUM_PERCENTAGE,
+        processingConfig.SUPERMAJORITY_PERCENTAGE,
+        'Linear',
+        null,
+        [prepared.TokenContractFactory.abi.project.source.wasmHash, true],
+        { value: one.muln(10) }
+      );
+      lootToken = prepared.TokenContractFactory.attach((await moloch.lootToken()).output.unwrap().toString());
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, 2 * processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+
+      // the summoner is granted some loot
+      await moloch.submitProposal(summoner.address, 0, lootGranted, 0, tokenAlpha.address, 0, tokenAlpha.address, 'loot', { signer: summoner });
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processProposal(firstProposalIndex, { signer: summoner });
+
+      // and votes YES on a second proposal
+      await moloch.submitProposal(applicant.address, 1, 0, 0, tokenAlpha.address, 0, tokenAlpha.address, 'share', { signer: applicant });
+      await moloch.sponsorProposal(secondProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(secondProposalIndex, yes, { signer: summoner });
+    });
+
+    it('require fail - loot is locked until the YES vote is processed', async () => {
+      await expect(lootToken.tx['transfer'](applicant.address, lootGranted, [], { signer: summoner }))
+        .to.not.emit(lootToken, 'Transfer');
+      await verifyBalance({ token: lootToken, address: summoner.address, expectedBalance: lootGranted });
+    });
+
+    it('happy case - loot moves once the YES vote is processed', async () => {
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processProposal(secondProposalIndex, { signer: summoner });
+
+      await expect(lootToken.tx['transfer'](applicant.address, lootGranted, [], { signer: summoner }))
+        .to.emit(lootToken, 'Transfer');
+      const member = (await moloch.members(applicant.address)).output.unwrap();
+      assert.equal(+member.loot, lootGranted);
+    });
+  });
+
+  describe('action proposals', () => {
+    let tokenAlpha, tokenBeta, moloch;
+    let creator, summoner, applicant;
+    const approveSelector = '0xb20f1bbd';
+    const approvedAmount = 100;
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      // a token the moloch does not account for, which actions can target
+      tokenBeta = await prepared.TokenContractFactory.deploy('new', processingConfig.TOKEN_SUPPLY);
+      moloch = await prepared.SubMolochContractFactory.deploy('new',
+        summoner.address,
+        [tokenAlpha.address, NATIVE],
+        processingConfig.PERIOD_DURATION_IN_SECONDS,
+        processingConfig.VOTING_DURATON_IN_PERIODS,
+        processingConfig.GRACE_DURATON_IN_PERIODS,
+        processingConfig.PROPOSAL_DEPOSIT,
+        processingConfig.DILUTION_BOUND,
+        processingConfig.PROCESSING_REWARD,
+        processingConfig.QUORUM_PERCENTAGE,
+        processingConfig.SUPERMAJORITY_PERCENTAGE
+      );
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+    });
+
+    const approveInput = () => api.createType('(AccountId, Balance)', [applicant.address, approvedAmount]).toU8a();
+
+    it('happy case - passed action is executed by the moloch', async () => {
+      await moloch.submitActionProposal(tokenBeta.address, approveSelector, approveInput(), 0, 'approve applicant', { signer: applicant });
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+
+      await expect(moloch.processProposal(firstProposalIndex, { signer: summoner }))
+        .to.not.emit(moloch, 'ProcessProposal');
+      await expect(moloch.processActionProposal(firstProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'ExecuteAction');
+
+      await verifyAllowance({
+        token: tokenBeta,
+        owner: moloch.address,
+        spender: applicant.address,
+        expectedAllowance: approvedAmount
+      });
+    });
+
+    it('happy case - failed action is not executed', async () => {
+      await moloch.submitActionProposal(tokenBeta.address, approveSelector, approveInput(), 0, 'approve applicant', { signer: applicant });
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, no, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+
+      await expect(moloch.processActionProposal(firstProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'ProcessActionProposal')
+        .and.not.emit(moloch, 'ExecuteAction');
+
+      await verifyAllowance({
+        token: tokenBeta,
+        owner: moloch.address,
+        spender: applicant.address,
+        expectedAllowance: 0
+      });
+    });
+
+    it('require fail - action cannot target the moloch', async () => {
+      await expect(moloch.submitActionProposal(moloch.address, approveSelector, approveInput(), 0, 'call moloch', { signer: applicant }))
+        .to.not.emit(moloch, 'SubmitProposal');
+    });
+
+    it('require fail - action cannot target a token the moloch accounts for', async () => {
+      await expect(moloch.submitActionProposal(tokenAlpha.address, approveSelector, approveInput(), 0, 'approve applicant', { signer: applicant }))
+        .to.not.emit(moloch, 'SubmitProposal');
+    });
+
+    it('happy case - action fails when the guild bank cannot pay its value', async () => {
+      await expect(moloch.submitActionProposal(tokenBeta.address, approveSelector, approveInput(), 1, 'pay token', { signer: applicant }))
+        .to.emit(moloch, 'SubmitProposal');
+      // the guild bank holds no native currency, so the proposal fails on processing
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+
+      await expect(moloch.processActionProposal(firstProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'ProcessActionProposal')
+        .and.not.emit(moloch, 'ExecuteAction');
+    });
+  });
+
+  describe('parameters proposals', () => {
+    let tokenAlpha, moloch;
+    let creator, summoner, applicant;
+    const newParameters = {
+      votingPeriodLength: processingConfig.VOTING_DURATON_IN_PERIODS + 1,
+      gracePeriodLength: processingConfig.GRACE_DURATON_IN_PERIODS + 1,
+      proposalDeposit: processingConfig.PROPOSAL_DEPOSIT * 2,
+      dilutionBound: processingConfig.DILUTION_BOUND,
+      processingReward: processingConfig.PROCESSING_REWARD * 2
+    };
+
+    const submitParametersProposal = (parameters, signer) => moloch.submitParametersProposal(
+      parameters.votingPeriodLength,
+      parameters.gracePeriodLength,
+      parameters.proposalDeposit,
+      parameters.dilutionBound,
+      parameters.processingReward,
+      'change parameters',
+      { signer }
+    );
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      moloch = prepared.submoloch;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, initSummonerBalance, { signer: summoner });
+    });
+
+    it('happy case - passed proposal updates the parameters', async () => {
+      await submitParametersProposal(newParameters, applicant);
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+
+      await expect(moloch.processParametersProposal(firstProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'ParametersUpdated');
+
+      assert.equal(+(await moloch.votingPeriodLength()).output, newParameters.votingPeriodLength);
+      assert.equal(+(await moloch.gracePeriodLength()).output, newParameters.gracePeriodLength);
+      assert.equal(+(await moloch.proposalDeposit()).output, newParameters.proposalDeposit);
+      assert.equal(+(await moloch.processingReward()).output, newParameters.processingReward);
+    });
+
+    it('happy case - failed proposal keeps the parameters', async () => {
+      await submitParametersProposal(newParameters, applicant);
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, no, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+
+      await expect(moloch.processParametersProposal(firstProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'ProcessParametersProposal')
+        .and.not.emit(moloch, 'ParametersUpdated');
+
+      assert.equal(+(await moloch.votingPeriodLength()).output, processingConfig.VOTING_DURATON_IN_PERIODS);
+      assert.equal(+(await moloch.proposalDeposit()).output, processingConfig.PROPOSAL_DEPOSIT);
+    });
+
+    it('happy case - proposals sponsored before the update keep their parameters', async () => {
+      await submitParametersProposal(newParameters, applicant);
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      await submitParametersProposal(newParameters, applicant);
+      await moloch.sponsorProposal(secondProposalIndex, { signer: summoner });
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processParametersProposal(firstProposalIndex, { signer: summoner });
+
+      const parameters = (await moloch.proposalParameters(secondProposalIndex)).output.unwrap();
+      assert.equal(+parameters.votingPeriodLength, processingConfig.VOTING_DURATON_IN_PERIODS);
+      assert.equal(+parameters.proposalDeposit, processingConfig.PROPOSAL_DEPOSIT);
+
+      await moveForwardPeriods(1);
+      await expect(moloch.processParametersProposal(secondProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'DepositSettled');
+    });
+
+    it('require fail - voting period cannot be 0', async () => {
+      await expect(submitParametersProposal({ ...newParameters, votingPeriodLength: 0 }, applicant))
+        .to.not.emit(moloch, 'SubmitProposal');
+    });
+
+    it('require fail - deposit cannot be smaller than processing reward', async () => {
+      await expect(submitParametersProposal({ ...newParameters, proposalDeposit: 1, processingReward: 2 }, applicant))
+        .to.not.emit(moloch, 'SubmitProposal');
+    });
+
+    it('require fail - standard processing rejects parameters proposals', async () => {
+      await submitParametersProposal(newParameters, applicant);
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS + 1);
+
+      await expect(moloch.processProposal(firstProposalIndex, { signer: summoner }))
+        .to.not.emit(moloch, 'ProcessProposal');
+    });
+  });
+
+  describe('dewhitelist proposals', () => {
+    let tokenAlpha, tokenBeta, moloch;
+    let creator, summoner, applicant;
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      tokenBeta = await prepared.TokenContractFactory.deploy('new', processingConfig.TOKEN_SUPPLY);
+      moloch = await prepared.SubMolochContractFactory.deploy('new',
+        summoner.address,
+        [tokenAlpha.address, tokenBeta.address],
+        processingConfig.PERIOD_DURATION_IN_SECONDS,
+        processingConfig.VOTING_DURATON_IN_PERIODS,
+        processingConfig.GRACE_DURATON_IN_PERIODS,
+        processingConfig.PROPOSAL_DEPOSIT,
+        processingConfig.DILUTION_BOUND,
+        processingConfig.PROCESSING_REWARD,
+        processingConfig.QUORUM_PERCENTAGE,
+        processingConfig.SUPERMAJORITY_PERCENTAGE
+      );
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+      // the guild bank holds some of the token to dewhitelist
+      await tokenBeta.tx['transfer'](moloch.address, standardTribute, TO_GUILD_BANK, { signer: creator });
+    });
+
+    const passDewhitelistProposal = async () => {
+      await moloch.submitDewhitelistProposal(tokenBeta.address, 'remove beta', { signer: applicant });
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processDewhitelistProposal(firstProposalIndex, { signer: summoner });
+    };
+
+    it('happy case - passed proposal removes the token from the whitelist', async () => {
+      await passDewhitelistProposal();
+
+      assert.equal((await moloch.tokenWhitelist(tokenBeta.address)).output, false);
+      assert.equal((await moloch.approvedTokens(0)).output.toHex(), tokenAlpha.address.toHex());
+      assert.equal((await moloch.delistedTokens(0)).output.unwrap().toHex(), tokenBeta.address.toHex());
+
+      await expect(moloch.submitProposal(
+        applicant.address,
+        standardShareRequest,
+        standardLootRequest,
+        standardTribute,
+        tokenBeta.address,
+        0,
+        tokenAlpha.address,
+        'all hail moloch',
+        { signer: applicant }
+      )).to.not.emit(moloch, 'SubmitProposal');
+    });
+
+    it('happy case - delisted balance is claimable through ragequit', async () => {
+      await passDewhitelistProposal();
+
+      await expect(moloch.ragequit(1, 0, { signer: summoner }))
+        .to.emit(moloch, 'Ragequit');
+
+      await verifyInternalBalances({
+        moloch: moloch,
+        token: tokenBeta,
+        userBalances: {
+          [GUILD]: 0,
+          [summoner.address]: standardTribute
+        }
+      });
+      assert.isTrue((await moloch.delistedTokens(0)).output.isNone);
+    });
+
+    it('require fail - deposit token cannot be dewhitelisted', async () => {
+      await expect(moloch.submitDewhitelistProposal(tokenAlpha.address, 'remove alpha', { signer: applicant }))
+        .to.not.emit(moloch, 'SubmitProposal');
+    });
+
+    it('require fail - token must be whitelisted', async () => {
+      await expect(moloch.submitDewhitelistProposal(applicant.address, 'remove nothing', { signer: applicant }))
+        .to.not.emit(moloch, 'SubmitProposal');
+    });
+  });
+
+  describe('state export', () => {
+    let tokenAlpha, moloch, successor, SubMolochContractFactory;
+    let creator, summoner, applicant;
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      moloch = prepared.submoloch;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      SubMolochContractFactory = prepared.SubMolochContractFactory;
+      successor = await SubMolochContractFactory.deploy('newSuccessor', moloch.address);
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, initSummonerBalance, { signer: summoner });
+    });
+
+    const approveExport = async () => {
+      await moloch.submitExportProposal(successor.address, 'move the dao', { signer: applicant });
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      // an open proposal is carried over along with the votes on it
+      await moloch.submitWhitelistProposal(applicant.address, 'whitelist applicant', { signer: summoner });
+      await moloch.sponsorProposal(secondProposalIndex, { signer: summoner });
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moloch.submitVote(secondProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await expect(moloch.processExportProposal(firstProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'ProcessExportProposal');
+    };
+
+    it('require fail - export must be approved', async () => {
+      assert.isTrue((await moloch.exportState()).output.isErr);
+      assert.equal((await successor.predecessor()).output.toHex(), moloch.address.toHex());
+      await expect(successor.importState()).to.not.emit(successor, 'ImportState');
+      assert.isTrue((await successor.paused()).output);
+      await expect(moloch.migrateFunds(tokenAlpha.address)).to.not.emit(moloch, 'MigrateFunds');
+    });
+
+    it('happy case - the successor rebuilds an identical moloch', async () => {
+      await approveExport();
+      assert.equal((await moloch.successor()).output.toHex(), successor.address.toHex());
+
+      await expect(successor.importState())
+        .to.emit(successor, 'ImportState')
+        .withArgs(moloch.address);
+      assert.isTrue((await successor.predecessor()).output.isNone);
+      assert.isFalse((await successor.paused()).output);
+
+      for (const getter of ['totalShares', 'totalLoot', 'proposalCount', 'votingPeriodLength', 'proposalDeposit', 'storageVersion']) {
+        assert.equal((await successor[getter]()).output.toString(), (await moloch[getter]()).output.toString(), getter);
+      }
+      assert.equal((await successor.members(summoner.address)).output.toString(), (await moloch.members(summoner.address)).output.toString());
+      assert.equal((await successor.proposals(secondProposalIndex)).output.toString(), (await moloch.proposals(secondProposalIndex)).output.toString());
+      assert.equal((await successor.approvedTokens(0)).output.toHex(), tokenAlpha.address.toHex());
+
+      await verifyInternalBalances({
+        moloch: successor,
+        token: tokenAlpha,
+        userBalances: {
+          [summoner.address]: processingConfig.PROPOSAL_DEPOSIT,
+          [ESCROW]: processingConfig.PROPOSAL_DEPOSIT,
+          [TOTAL]: processingConfig.PROPOSAL_DEPOSIT * 2
+        }
+      });
+
+      // the state can only be imported once
+      await expect(successor.importState()).to.not.emit(successor, 'ImportState');
+
+      await expect(moloch.migrateFunds(tokenAlpha.address))
+        .to.emit(moloch, 'MigrateFunds')
+        .withArgs(successor.address, tokenAlpha.address, processingConfig.PROPOSAL_DEPOSIT * 2);
+      await verifyBalance({
+        token: tokenAlpha,
+        address: successor.address,
+        expectedBalance: processingConfig.PROPOSAL_DEPOSIT * 2
+      });
+      await expect(moloch.migrateFunds(tokenAlpha.address)).to.not.emit(moloch, 'MigrateFunds');
+
+      await expect(successor.processWhitelistProposal(secondProposalIndex, { signer: summoner }))
+        .to.emit(successor, 'ProcessWhitelistProposal');
+      assert.equal((await successor.tokenWhitelist(applicant.address)).output, true);
+    });
+
+    it('require fail - the moloch is frozen once the export is approved', async () => {
+      await approveExport();
+      const exported = (await moloch.exportState()).output.asOk.toHex();
+
+      await expect(moloch.processWhitelistProposal(secondProposalIndex, { signer: summoner }))
+        .to.not.emit(moloch, 'ProcessWhitelistProposal');
+      await expect(moloch.submitVote(secondProposalIndex, no, { signer: summoner }))
+        .to.not.emit(moloch, 'SubmitVote');
+      await expect(moloch.ragequit(1, 0, { signer: summoner }))
+        .to.not.emit(moloch, 'Ragequit');
+      await expect(moloch.withdrawBalance(tokenAlpha.address, 1, { signer: summoner }))
+        .to.not.emit(moloch, 'Withdraw');
+      await expect(moloch.submitPauseVote(true, { signer: summoner }))
+        .to.not.emit(moloch, 'SubmitPauseVote');
+
+      assert.equal((await moloch.exportState()).output.asOk.toHex(), exported);
+    });
+
+    it('require fail - only the approved successor imports the state', async () => {
+      const other = await SubMolochContractFactory.deploy('newSuccessor', moloch.address);
+      await approveExport();
+
+      await expect(other.importState()).to.not.emit(other, 'ImportState');
+      assert.isTrue((await other.paused()).output);
+      assert.equal((await other.predecessor()).output.toHex(), moloch.address.toHex());
+    });
+
+    it('happy case - a moloch is rebuilt from the exported snapshot', async () => {
+      await approveExport();
+      const rebuilt = await SubMolochContractFactory.deploy('fromSnapshot', (await moloch.exportState()).output.asOk);
+
+      for (const getter of ['totalShares', 'totalLoot', 'proposalCount', 'votingPeriodLength', 'storageVersion']) {
+        assert.equal((await rebuilt[getter]()).output.toString(), (await moloch[getter]()).output.toString(), getter);
+      }
+      assert.equal((await rebuilt.members(summoner.address)).output.toString(), (await moloch.members(summoner.address)).output.toString());
+      assert.isFalse((await rebuilt.paused()).output);
+
+      // the tokens are only migrated to the successor named by the export proposal
+      await expect(moloch.migrateFunds(tokenAlpha.address))
+        .to.emit(moloch, 'MigrateFunds')
+        .withArgs(successor.address, tokenAlpha.address, processingConfig.PROPOSAL_DEPOSIT * 2);
+      await verifyBalance({ token: tokenAlpha, address: rebuilt.address, expectedBalance: 0 });
+    });
+  });
+
+  describe('vote delegation', () => {
+    let tokenAlpha, moloch;
+    let creator, summoner, applicant, otherMember;
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      moloch = prepared.submoloch;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      otherMember = prepared.applicant2;
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, initSummonerBalance, { signer: summoner });
+      await tokenAlpha.tx['transfer'](applicant.address, standardTribute, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, standardTribute, { signer: applicant });
+
+      // the applicant joins with standardShareRequest shares
+      await moloch.submitProposal(
+        applicant.address,
+        standardShareRequest,
+        0,
+        standardTribute,
+        tokenAlpha.address,
+        0,
+        tokenAlpha.address,
+        'all hail moloch',
+        { signer: applicant }
+      );
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processProposal(firstProposalIndex, { signer: summoner });
+
+      await moloch.submitWhitelistProposal(applicant.address, 'whitelist applicant', { signer: summoner });
+      await moloch.sponsorProposal(secondProposalIndex, { signer: summoner });
+    });
+
+    it('happy case - delegate votes with the shares of its delegators', async () => {
+      await expect(moloch.delegateVotes(summoner.address, { signer: applicant }))
+        .to.emit(moloch, 'DelegateVotes');
+      assert.equal((await moloch.voteDelegate(applicant.address)).output.unwrap().toHex(), summoner.address.toHex());
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(secondProposalIndex, yes, { signer: summoner });
+
+      const proposal = (await moloch.proposals(secondProposalIndex)).output.unwrap();
+      assert.equal(+proposal.yesVotes, 1 + standardShareRequest);
+      assert.equal(+proposal.noVotes, 0);
+    });
+
+    it('happy case - vote of the delegator overrides its delegate', async () => {
+      await moloch.delegateVotes(summoner.address, { signer: applicant });
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(secondProposalIndex, yes, { signer: summoner });
+      await moloch.submitVote(secondProposalIndex, no, { signer: applicant });
+
+      const proposal = (await moloch.proposals(secondProposalIndex)).output.unwrap();
+      assert.equal(+proposal.yesVotes, 1);
+      assert.equal(+proposal.noVotes, standardShareRequest);
+    });
+
+    it('happy case - revoked delegation is not counted', async () => {
+      await moloch.delegateVotes(summoner.address, { signer: applicant });
+      await expect(moloch.undelegateVotes({ signer: applicant }))
+        .to.emit(moloch, 'DelegateVotes');
+      assert.isTrue((await moloch.voteDelegate(applicant.address)).output.isNone);
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(secondProposalIndex, yes, { signer: summoner });
+
+      const proposal = (await moloch.proposals(secondProposalIndex)).output.unwrap();
+      assert.equal(+proposal.yesVotes, 1);
+    });
+
+    it('happy case - re-delegated votes are not counted twice', async () => {
+      // another member joins with standardShareRequest shares
+      await tokenAlpha.tx['transfer'](otherMember.address, standardTribute, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, standardTribute, { signer: otherMember });
+      await moloch.submitProposal(
+        otherMember.address,
+        standardShareRequest,
+        0,
+        standardTribute,
+        tokenAlpha.address,
+        0,
+        tokenAlpha.address,
+        'all hail moloch',
+        { signer: otherMember }
+      );
+      await moloch.sponsorProposal(thirdProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(thirdProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processWhitelistProposal(secondProposalIndex, { signer: summoner });
+      await moloch.processProposal(thirdProposalIndex, { signer: summoner });
+
+      const fourthProposalIndex = 3;
+      await moloch.submitWhitelistProposal(otherMember.address, 'whitelist other member', { signer: summoner });
+      await moloch.sponsorProposal(fourthProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+
+      await moloch.delegateVotes(summoner.address, { signer: applicant });
+      await moloch.submitVote(fourthProposalIndex, yes, { signer: summoner });
+      await moloch.delegateVotes(otherMember.address, { signer: applicant });
+      await moloch.submitVote(fourthProposalIndex, yes, { signer: otherMember });
+
+      // the shares of the delegator stay with the first delegate's vote
+      const proposal = (await moloch.proposals(fourthProposalIndex)).output.unwrap();
+      assert.equal(+proposal.yesVotes, 1 + standardShareRequest * 2);
+    });
+
+    it('require fail - delegation cannot loop', async () => {
+      await moloch.delegateVotes(summoner.address, { signer: applicant });
+      await expect(moloch.delegateVotes(applicant.address, { signer: summoner }))
+        .to.not.emit(moloch, 'DelegateVotes');
+    });
+
+    it('require fail - cannot delegate to self', async () => {
+      await expect(moloch.delegateVotes(applicant.address, { signer: applicant }))
+        .to.not.emit(moloch, 'DelegateVotes');
+    });
+  });
+
+  describe('voting mode', () => {
+    let tokenAlpha, moloch;
+    let creator, summoner, applicant, otherMember;
+
+    const deploy = async (votingMode) => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      otherMember = prepared.applicant2;
+      moloch = await prepared.SubMolochContractFactory.deploy('newWithOptions',
+        summoner.address,
+        [tokenAlpha.address],
+        processingConfig.PERIOD_DURATION_IN_SECONDS,
+        processingConfig.VOTING_DURATON_IN_PERIODS,
+        processingConfig.GRACE_DURATON_IN_PERIODS,
+        processingConfig.PROPOSAL_DEPOSIT,
+        processingConfig.DILUTION_BOUND,
+        processingConfig.PROCESSING_REWARD,
+        processingConfig.QUORUM_PERCENTAGE,
+        processingConfig.SUPERMAJORITY_PERCENTAGE,
+        votingMode,
+        null,
+        null
+      );
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, initSummonerBalance, { signer: summoner });
+
+      // the applicant joins with 16 shares and the other member with 24 shares
+      for (const [index, member, shares] of [[firstProposalIndex, applicant, 16], [secondProposalIndex, otherMember, 24]]) {
+        await tokenAlpha.tx['transfer'](member.address, standardTribute, [], { signer: creator });
+        await tokenAlpha.approve(moloch.address, standardTribute, { signer: member });
+        await moloch.submitProposal(member.address, shares, 0, standardTribute, tokenAlpha.address, 0, tokenAlpha.address, 'all hail moloch', { signer: member });
+        await moloch.sponsorProposal(index, { signer: summoner });
+      }
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moloch.submitVote(secondProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processProposal(firstProposalIndex, { signer: summoner });
+      await moloch.processProposal(secondProposalIndex, { signer: summoner });
+
+      // the summoner and the applicant vote YES, the other member votes NO
+      await moloch.submitWhitelistProposal(creator.address, 'whitelist creator', { signer: summoner });
+      await moloch.sponsorProposal(thirdProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(thirdProposalIndex, yes, { signer: summoner });
+      await moloch.submitVote(thirdProposalIndex, yes, { signer: applicant });
+      await moloch.submitVote(thirdProposalIndex, no, { signer: otherMember });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processWhitelistProposal(thirdProposalIndex, { signer: summoner });
+    };
+
+    it('happy case - linear votes weigh one per share', async () => {
+      await deploy('Linear');
+
+      const proposal = (await moloch.proposals(thirdProposalIndex)).output.unwrap();
+      assert.equal(+proposal.yesVotes, 1 + 16);
+      assert.equal(+proposal.noVotes, 24);
+      assert.equal((await moloch.tokenWhitelist(creator.address)).output, false);
+    });
+
+    it('happy case - quadratic votes weigh the square root of the shares', async () => {
+      await deploy('Quadratic');
+
+      // the same ballots pass once the large member weighs less
+      const proposal = (await moloch.proposals(thirdProposalIndex)).output.unwrap();
+      assert.equal(+proposal.yesVotes, 1 + 4);
+      assert.equal(+proposal.noVotes, 4);
+      assert.equal((await moloch.tokenWhitelist(creator.address)).output, true);
+    });
+  });
+
+  describe('conviction voting', () => {
+    let tokenAlpha, moloch;
+    let creator, summoner, applicant;
+    const paymentRequested = 1;
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      moloch = prepared.submoloch;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      // the guild bank holds standardTribute tokens
+      await tokenAlpha.tx['transfer'](moloch.address, standardTribute, TO_GUILD_BANK, { signer: creator });
+      await moloch.submitConvictionProposal(applicant.address, paymentRequested, tokenAlpha.address, 'grant', { signer: applicant });
+    });
+
+    it('happy case - proposal stores the hash of its details', async () => {
+      assert.equal((await moloch.convictionProposals(0)).output.unwrap().details.toHex(), blake2AsHex('grant', 256));
+    });
+
+    it('happy case - proposal is paid once conviction crosses the threshold', async () => {
+      await expect(moloch.stakeConviction(0, 1, { signer: summoner }))
+        .to.emit(moloch, 'StakeConviction');
+      assert.equal(+(await moloch.stakedShares(summoner.address)).output, 1);
+
+      await expect(moloch.executeConvictionProposal(0, { signer: summoner }))
+        .to.not.emit(moloch, 'ExecuteConvictionProposal');
+
+      await moveForwardPeriods(3);
+      const conviction = +(await moloch.conviction(0)).output.unwrap();
+      const threshold = +(await moloch.convictionThreshold(0)).output.unwrap();
+      assert.isAtLeast(conviction, threshold);
+
+      await expect(moloch.executeConvictionProposal(0, { signer: summoner }))
+        .to.emit(moloch, 'ExecuteConvictionProposal');
+
+      await verifyInternalBalances({
+        moloch: moloch,
+        token: tokenAlpha,
+        userBalances: {
+          [GUILD]: standardTribute - paymentRequested,
+          [applicant.address]: paymentRequested
+        }
+      });
+    });
+
+    it('require fail - staked shares cannot be ragequit', async () => {
+      // ragequit needs a processed proposal in the queue
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+      await moloch.submitWhitelistProposal(applicant.address, 'whitelist applicant', { signer: summoner });
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS + 1);
+      await moloch.processWhitelistProposal(firstProposalIndex, { signer: summoner });
+
+      await moloch.stakeConviction(0, 1, { signer: summoner });
+      await expect(moloch.ragequit(1, 0, { signer: summoner }))
+        .to.not.emit(moloch, 'Ragequit');
+
+      await moloch.unstakeConviction(0, 1, { signer: summoner });
+      await expect(moloch.ragequit(1, 0, { signer: summoner }))
+        .to.emit(moloch, 'Ragequit');
+    });
+
+    it('happy case - a guild kick withdraws the stakes of the kicked member', async () => {
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, initSummonerBalance, { signer: summoner });
+      await tokenAlpha.tx['transfer'](applicant.address, standardTribute, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, standardTribute, { signer: applicant });
+
+      // the applicant joins with one share and stakes it
+      await moloch.submitProposal(applicant.address, 1, 0, standardTribute, tokenAlpha.address, 0, tokenAlpha.address, 'all hail moloch', { signer: applicant });
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processProposal(firstProposalIndex, { signer: summoner });
+      await moloch.stakeConviction(0, 1, { signer: applicant });
+
+      await moloch.submitGuildkickProposal(applicant.address, 'kick applicant', { signer: summoner });
+      await moloch.sponsorProposal(secondProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(secondProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await expect(moloch.processGuildkickProposal(secondProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'UnstakeConviction');
+
+      assert.equal(+(await moloch.stakedShares(applicant.address)).output, 0);
+      assert.equal(+(await moloch.convictionStake(0, applicant.address)).output, 0);
+      assert.equal(+(await moloch.convictionProposals(0)).output.unwrap().staked, 0);
+      await expect(moloch.stakeConviction(0, 1, { signer: applicant }))
+        .to.not.emit(moloch, 'StakeConviction');
+    });
+
+    it('require fail - cannot stake more than the member shares', async () => {
+      await expect(moloch.stakeConviction(0, 2, { signer: summoner }))
+        .to.not.emit(moloch, 'StakeConviction');
+    });
+
+    it('require fail - request above the spending limit never passes', async () => {
+      await moloch.submitConvictionProposal(applicant.address, standardTribute, tokenAlpha.address, 'grant', { signer: applicant });
+      assert.isTrue((await moloch.convictionThreshold(1)).output.isNone);
+    });
+  });
+
+  describe('multi-option proposals', () => {
+    let tokenAlpha, moloch;
+    let creator, summoner, applicant;
+    const paymentRequested = 10;
+    let options;
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      moloch = prepared.submoloch;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+      // the guild bank holds standardTribute tokens
+      await tokenAlpha.tx['transfer'](moloch.address, standardTribute, TO_GUILD_BANK, { signer: creator });
+
+      options = [
+        null,
+        { applicant: applicant.address, paymentRequested, paymentToken: tokenAlpha.address },
+        null
+      ];
+    });
+
+    const submitAndSponsor = async (method) => {
+      await moloch.submitMultiOptionProposal(options, method, 'pick a grant', { signer: applicant });
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+    };
+
+    it('happy case - payment of the winning option is made', async () => {
+      await submitAndSponsor('Plurality');
+      await expect(moloch.submitRankedVote(firstProposalIndex, [1, 0], { signer: summoner }))
+        .to.emit(moloch, 'SubmitRankedVote');
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+
+      await expect(moloch.processMultiOptionProposal(firstProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'OptionTally');
+
+      await verifyInternalBalances({
+        moloch: moloch,
+        token: tokenAlpha,
+        userBalances: {
+          [GUILD]: standardTribute - paymentRequested,
+          [applicant.address]: paymentRequested
+        }
+      });
+    });
+
+    it('happy case - winning option without payment pays nothing', async () => {
+      await submitAndSponsor('InstantRunoff');
+      await moloch.submitRankedVote(firstProposalIndex, [2], { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+
+      await expect(moloch.processMultiOptionProposal(firstProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'ProcessMultiOptionProposal');
+
+      await verifyInternalBalance({
+        moloch: moloch,
+        token: tokenAlpha,
+        user: GUILD,
+        expectedBalance: standardTribute
+      });
+    });
+
+    it('require fail - yes/no votes are rejected', async () => {
+      await submitAndSponsor('Plurality');
+      await expect(moloch.submitVote(firstProposalIndex, yes, { signer: summoner }))
+        .to.not.emit(moloch, 'SubmitVote');
+    });
+
+    it('require fail - ranking must hold distinct options', async () => {
+      await submitAndSponsor('Plurality');
+      await expect(moloch.submitRankedVote(firstProposalIndex, [1, 1], { signer: summoner }))
+        .to.not.emit(moloch, 'SubmitRankedVote');
+      await expect(moloch.submitRankedVote(firstProposalIndex, [3], { signer: summoner }))
+        .to.not.emit(moloch, 'SubmitRankedVote');
+    });
+
+    it('require fail - needs at least two options', async () => {
+      await expect(moloch.submitMultiOptionProposal([null], 'Plurality', 'pick nothing', { signer: applicant }))
+        .to.not.emit(moloch, 'SubmitProposal');
+    });
+  });
+
+  describe('guardian', () => {
+    let tokenAlpha, moloch;
+    let creator, summoner, applicant, guardian;
+    const guardianPeriods = 100;
+
+    const deployWithGuardian = async (prepared, periods, votingMode = 'Linear') => {
+      return prepared.SubMolochContractFactory.deploy('newWithOptions',
+        summoner.address,
+        [tokenAlpha.address],
+        processingConfig.PERIOD_DURATION_IN_SECONDS,
+        processingConfig.VOTING_DURATON_IN_PERIODS,
+        processingConfig.GRACE_DURATON_IN_PERIODS,
+        processingConfig.PROPOSAL_DEPOSIT,
+        processingConfig.DILUTION_BOUND,
+        processingConfig.PROCESSING_REWARD,
+        processingConfig.QUORUM_PERCENTAGE,
+        processingConfig.SUPERMAJORITY_PERCENTAGE,
+        votingMode,
+        [guardian.address, periods],
+        null
+      );
+    };
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      guardian = prepared.applicant2;
+      moloch = await deployWithGuardian(prepared, guardianPeriods);
+
+      await tokenAlpha.tx['transfer'](applicant.address, standardTribute, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, standardTribute, { signer: applicant });
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, 2 * processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+
+      await moloch.submitProposal(
+        applicant.address,
+        standardShareRequest,
+        standardLootRequest,
+        standardTribute,
+        tokenAlpha.address,
+        0,
+        tokenAlpha.address,
+        'all hail moloch',
+        { signer: applicant }
+      );
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+    });
+
+    it('happy case - veto fails the proposal and refunds the tribute and the deposit', async () => {
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+
+      await expect(moloch.vetoProposal(firstProposalIndex, { signer: guardian }))
+        .to.emit(moloch, 'VetoProposal');
+
+      await verifyFlags({
+        moloch: moloch,
+        proposalId: firstProposalIndex,
+        expectedFlags: [true, true, false, false, false, false]
+      });
+      await verifyInternalBalances({
+        moloch: moloch,
+        token: tokenAlpha,
+        userBalances: {
+          [ESCROW]: 0,
+          [GUILD]: 0,
+          [applicant.address]: standardTribute,
+          [summoner.address]: processingConfig.PROPOSAL_DEPOSIT
+        }
+      });
+
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await expect(moloch.processProposal(firstProposalIndex, { signer: summoner }))
+        .to.not.emit(moloch, 'ProcessProposal');
+    });
+
+    it('require fail - only the guardian can veto', async () => {
+      await expect(moloch.vetoProposal(firstProposalIndex, { signer: summoner }))
+        .to.not.emit(moloch, 'VetoProposal');
+    });
+
+    it('happy case - the guardian pauses and unpauses', async () => {
+      await expect(moloch.pause({ signer: guardian }))
+        .to.emit(moloch, 'Paused');
+      await expect(moloch.withdrawBalance(tokenAlpha.address, 1, { signer: summoner }))
+        .to.not.emit(moloch, 'Withdraw');
+      await expect(moloch.unpause({ signer: guardian }))
+        .to.emit(moloch, 'Unpaused');
+      assert.isFalse((await moloch.paused()).output);
+    });
+
+    it('require fail - the guardian role expires', async () => {
+      const prepared = { SubMolochContractFactory: await getContractFactory('submoloch', creator) };
+      const shortLived = await deployWithGuardian(prepared, 1);
+      assert.equal((await shortLived.guardian()).output.unwrap().toString(), guardian.address);
+
+      await moveForwardPeriods(1);
+      assert.isTrue((await shortLived.guardian()).output.isNone);
+    });
+
+    it('happy case - the guardian is summoned along with another voting mode', async () => {
+      const prepared = { SubMolochContractFactory: await getContractFactory('submoloch', creator) };
+      const quadratic = await deployWithGuardian(prepared, guardianPeriods, 'Quadratic');
+      assert.equal((await quadratic.votingMode()).output.toString(), 'Quadratic');
+      assert.equal((await quadratic.guardian()).output.unwrap().toString(), guardian.address);
+      assert.equal(+(await quadratic.guardianExpiryPeriod()).output, guardianPeriods);
+
+      await expect(quadratic.pause({ signer: guardian }))
+        .to.emit(quadratic, 'Paused');
+    });
+
+    it('happy case - a passed proposal removes the guardian', async () => {
+      await moloch.submitRemoveGuardianProposal('remove the guardian', { signer: applicant });
+      await moloch.sponsorProposal(secondProposalIndex, { signer: summoner });
+      await expect(moloch.vetoProposal(secondProposalIndex, { signer: guardian }))
+        .to.not.emit(moloch, 'VetoProposal');
+
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, no, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(secondProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+
+      await moloch.processProposal(firstProposalIndex, { signer: summoner });
+      await expect(moloch.processRemoveGuardianProposal(secondProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'GuardianRemoved');
+      assert.isTrue((await moloch.guardian()).output.isNone);
+    });
+  });
+
+  describe('pause', () => {
+    let tokenAlpha, moloch;
+    let creator, summoner, applicant;
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      moloch = prepared.submoloch;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+      await moloch.submitExportProposal(creator.address, 'export the state', { signer: applicant });
+    });
+
+    it('happy case - a supermajority pauses and unpauses', async () => {
+      assert.isFalse((await moloch.paused()).output);
+      await expect(moloch.submitPauseVote(true, { signer: summoner }))
+        .to.emit(moloch, 'Paused');
+      assert.isTrue((await moloch.paused()).output);
+
+      await expect(moloch.submitExportProposal(creator.address, 'export the state', { signer: applicant }))
+        .to.not.emit(moloch, 'SubmitProposal');
+      await expect(moloch.sponsorProposal(firstProposalIndex, { signer: summoner }))
+        .to.not.emit(moloch, 'SponsorProposal');
+
+      await expect(moloch.submitPauseVote(false, { signer: summoner }))
+        .to.emit(moloch, 'Unpaused');
+      await expect(moloch.sponsorProposal(firstProposalIndex, { signer: summoner }))
+        .to.emit(moloch, 'SponsorProposal');
+    });
+
+    it('happy case - ragequit stays available while paused', async () => {
+      await moloch.submitPauseVote(true, { signer: summoner });
+      await expect(moloch.ragequit(1, 0, { signer: summoner }))
+        .to.emit(moloch, 'Ragequit');
+    });
+
+    const applicantJoinsWithOneShare = async () => {
+      // the export proposal submitted above stays unsponsored
+      const joinProposalId = 1;
+      await tokenAlpha.approve(moloch.address, processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+      await tokenAlpha.tx['transfer'](applicant.address, standardTribute, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, standardTribute, { signer: applicant });
+      await moloch.submitProposal(applicant.address, 1, 0, standardTribute, tokenAlpha.address, 0, tokenAlpha.address, 'all hail moloch', { signer: applicant });
+      await moloch.sponsorProposal(joinProposalId, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processProposal(firstProposalIndex, { signer: summoner });
+    };
+
+    it('happy case - pause votes weigh the current shares of their voters', async () => {
+      await applicantJoinsWithOneShare();
+
+      await moloch.submitPauseVote(true, { signer: applicant });
+      assert.equal(+(await moloch.pauseVotesWeight()).output, 1);
+      assert.isFalse((await moloch.paused()).output);
+
+      // the vote no longer counts once its shares are ragequit
+      await moloch.ragequit(1, 0, { signer: applicant });
+      assert.equal(+(await moloch.pauseVotesWeight()).output, 0);
+      assert.isTrue((await moloch.hasVotedPause(applicant.address)).output);
+    });
+
+    it('happy case - anyone switches the pause state once a ragequit brings the votes over the threshold', async () => {
+      await applicantJoinsWithOneShare();
+      await moloch.submitPauseVote(true, { signer: applicant });
+      await expect(moloch.checkPauseThreshold({ signer: creator }))
+        .to.not.emit(moloch, 'Paused');
+
+      // the summoner never voted, the applicant holds every share left
+      await moloch.ragequit(1, 0, { signer: summoner });
+      assert.isFalse((await moloch.paused()).output);
+      await expect(moloch.checkPauseThreshold({ signer: creator }))
+        .to.emit(moloch, 'Paused')
+        .withArgs(creator.address);
+      assert.isTrue((await moloch.paused()).output);
+    });
+
+    it('require fail - only members with shares can vote to pause', async () => {
+      await expect(moloch.submitPauseVote(true, { signer: applicant }))
+        .to.not.emit(moloch, 'SubmitPauseVote');
+      assert.isFalse((await moloch.paused()).output);
+    });
+
+    it('require fail - only the guardian can pause directly', async () => {
+      await expect(moloch.pause({ signer: summoner }))
+        .to.not.emit(moloch, 'Paused');
+    });
+  });
+
+  describe('vesting', () => {
+    let tokenAlpha, moloch;
+    let creator, summoner, applicant;
+    const schedule = { cliffPeriods: 1, vestingPeriods: 2 };
+
+    beforeEach(async () => {
+      const prepared = await setup(processingConfig);
+      tokenAlpha = prepared.tokenAlpha;
+      moloch = prepared.submoloch;
+      creator = prepared.creator;
+      summoner = prepared.summoner;
+      applicant = prepared.applicant1;
+      await tokenAlpha.tx['transfer'](applicant.address, standardTribute, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, standardTribute, { signer: applicant });
+      await tokenAlpha.tx['transfer'](summoner.address, initSummonerBalance, [], { signer: creator });
+      await tokenAlpha.approve(moloch.address, processingConfig.PROPOSAL_DEPOSIT, { signer: summoner });
+
+      await moloch.submitVestingProposal(
+        applicant.address,
+        standardShareRequest,
+        standardLootRequest,
+        standardTribute,
+        tokenAlpha.address,
+        0,
+        tokenAlpha.address,
+        'all hail moloch, eventually',
+        schedule,
+        { signer: applicant }
+      );
+      await moloch.sponsorProposal(firstProposalIndex, { signer: summoner });
+      await moveForwardPeriods(1);
+      await moloch.submitVote(firstProposalIndex, yes, { signer: summoner });
+      await moveForwardPeriods(processingConfig.VOTING_DURATON_IN_PERIODS + processingConfig.GRACE_DURATON_IN_PERIODS);
+      await moloch.processProposal(firstProposalIndex, { signer: summoner });
+    });
+
+    it('happy case - granted shares and loot are held as unvested', async () => {
+      const member = (await moloch.members(applicant.address)).output.unwrap();
+      assert.equal(+member.shares, 0);
+      assert.equal(+member.loot, 0);
+      assert.equal(+member.unvestedShares, standardShareRequest);
+      assert.equal(+member.unvestedLoot, standardLootRequest);
+      assert.equal(+(await moloch.totalShares()).output, summonerShares);
+
+      await expect(moloch.ragequit(1, 0, { signer: applicant }))
+        .to.not.emit(moloch, 'Ragequit');
+    });
+
+    it('happy case - vested shares and loot are claimed', async () => {
+      await moveForwardPeriods(schedule.vestingPeriods);
+      await expect(moloch.claimVested({ signer: applicant }))
+        .to.emit(moloch, 'ClaimVested');
+
+      const member = (await moloch.members(applicant.address)).output.unwrap();
+      assert.equal(+member.shares, standardShareRequest);
+      assert.equal(+member.loot, standardLootRequest);
+      assert.equal(+member.unvestedShares, 0);
+      assert.equal(+member.unvestedLoot, 0);
+      assert.equal(+(await moloch.totalShares()).output, summonerShares + standardShareRequest);
+
+      await expect(moloch.ragequit(standardShareRequest, standardLootRequest, { signer: applicant }))
+        .to.emit(moloch, 'Ragequit');
+    });
+
+    it('require fail - nothing to claim before the cliff', async () => {
+      await expect(moloch.claimVested({ signer: applicant }))
+        .to.not.emit(moloch, 'ClaimVested');
+    });
+
+    it('require fail - vesting periods must be positive and cover the cliff', async () => {
+      for (const invalid of [{ cliffPeriods: 0, vestingPeriods: 0 }, { cliffPeriods: 3, vestingPeriods: 2 }]) {
+        await expect(moloch.submitVestingProposal(
+          applicant.address,
+          standardShareRequest,
+          0,
+          0,
+          tokenAlpha.address,
+          0,
+          tokenAlpha.address,
+          'all hail moloch, eventually',
+          invalid,
+          { signer: applicant }
+        )).to.not.emit(moloch, 'SubmitProposal');
+      }
+      assert.equal(+(await moloch.proposalCount()).output, 1);
+    });
+  });
+
 });